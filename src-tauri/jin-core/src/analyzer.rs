@@ -0,0 +1,234 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+use crate::config::JinConfig;
+use crate::interpolate;
+use crate::policies;
+use crate::environments::{load_order, EnvironmentMap};
+use crate::types::{
+    Duplicate, DuplicateKind, DuplicateValue, InferredType, MissingKeys, NormalizedEntry, ScanIssues,
+    SourceFormat,
+};
+
+/// Finds issues across all parsed entries. Keys are compared by `canonical_key`, which the
+/// scanner fills in from `config.keys` (see `keys::apply`).
+pub fn analyze_entries(entries: &[NormalizedEntry], config: &JinConfig) -> ScanIssues {
+    let map = environment_map(entries, config);
+    let duplicates = find_duplicates(entries);
+    let missing_by_env_file = find_missing_keys(entries, &map);
+    let references = interpolate::resolve(entries);
+    let policy_violations = policies::check(entries, &config.policies, &map);
+
+    ScanIssues {
+        duplicates,
+        missing_by_env_file,
+        parse_errors: Vec::new(), // Parse errors are collected during parsing
+        undefined_references: references.undefined,
+        reference_cycles: references.cycles,
+        exposed_secrets: Vec::new(), // Needs the git index, checked by the scanner
+        schema_violations: Vec::new(), // Validated by the scanner before flattening
+        policy_violations,
+        // Need the source index, built by the scanner when `[code]` is enabled
+        unused_keys: Vec::new(),
+        undefined_keys: Vec::new(),
+    }
+}
+
+/// Shown instead of secret values in duplicate reports.
+pub const MASKED_VALUE: &str = "********";
+
+fn find_duplicates(entries: &[NormalizedEntry]) -> Vec<Duplicate> {
+    // Canonical key -> file -> entry; a key repeated within one file keeps its last definition
+    let mut key_files: HashMap<&str, BTreeMap<&str, &NormalizedEntry>> = HashMap::new();
+
+    for entry in entries {
+        key_files
+            .entry(entry.canonical_key.as_str())
+            .or_default()
+            .insert(entry.source_file.as_str(), entry);
+    }
+
+    let mut duplicates: Vec<Duplicate> = key_files
+        .into_iter()
+        .filter(|(_, files)| files.len() > 1)
+        .map(|(key, files)| {
+            let values: HashSet<Option<String>> = files.values().map(|e| display_value(e)).collect();
+            let kind = if has_type_conflict(files.values().copied()) {
+                DuplicateKind::DifferentTypes
+            } else if values.len() > 1 {
+                DuplicateKind::DifferentValues
+            } else {
+                DuplicateKind::SameValue
+            };
+
+            Duplicate {
+                key: files.values().next().map(|e| e.key.clone()).unwrap_or_default(),
+                canonical_key: key.to_string(),
+                files: files.keys().map(|f| f.to_string()).collect(),
+                kind,
+                values: files
+                    .values()
+                    .map(|entry| {
+                        let is_secret = entry.shows_secret();
+                        DuplicateValue {
+                            file: entry.source_file.clone(),
+                            key: entry.key.clone(),
+                            value: if is_secret {
+                                Some(MASKED_VALUE.to_string())
+                            } else {
+                                display_value(entry)
+                            },
+                            inferred_type: entry.inferred_type,
+                            is_secret,
+                        }
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+
+    duplicates.sort_by(|a, b| a.key.cmp(&b.key));
+    duplicates
+}
+
+// The value as written, or as resolved for env values with references
+pub(crate) fn display_value(entry: &NormalizedEntry) -> Option<String> {
+    match entry.resolved_value.as_ref().or(entry.value.as_ref())? {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Null => None,
+        other => Some(other.to_string()),
+    }
+}
+
+// Env values are always strings, so they only conflict with a structured file's type when they
+// don't look like it (`PORT=abc` against `port: 8080`)
+pub(crate) fn has_type_conflict<'a>(entries: impl Iterator<Item = &'a NormalizedEntry> + Clone) -> bool {
+    let typed: HashSet<InferredType> = entries
+        .clone()
+        .filter(|e| !matches!(e.source_format, SourceFormat::Env))
+        .map(|e| e.inferred_type)
+        .collect();
+    if typed.len() > 1 {
+        return true;
+    }
+
+    match typed.into_iter().next() {
+        Some(expected) if expected != InferredType::String => entries
+            .filter(|e| matches!(e.source_format, SourceFormat::Env))
+            .any(|e| env_value_type(e) != expected),
+        _ => false,
+    }
+}
+
+/// The type a text value looks like: `8080` is a number, `true` a boolean.
+pub(crate) fn env_value_type(entry: &NormalizedEntry) -> InferredType {
+    match display_value(entry).as_deref() {
+        Some("true" | "false") => InferredType::Boolean,
+        Some(v) if v.parse::<f64>().is_ok() => InferredType::Number,
+        _ => InferredType::String,
+    }
+}
+
+// Environments of the files that have entries
+fn environment_map(entries: &[NormalizedEntry], config: &JinConfig) -> EnvironmentMap {
+    let mut file_kinds: Vec<(String, bool)> = Vec::new();
+    let mut seen: HashSet<&str> = HashSet::new();
+    for entry in entries {
+        if seen.insert(entry.source_file.as_str()) {
+            file_kinds.push((entry.source_file.clone(), entry.source_format == SourceFormat::Env));
+        }
+    }
+    EnvironmentMap::build(&file_kinds, &config.environments)
+}
+
+fn find_missing_keys(entries: &[NormalizedEntry], map: &EnvironmentMap) -> Vec<MissingKeys> {
+    let mut keys_by_file: HashMap<&str, HashSet<&str>> = HashMap::new();
+    let mut env_files: HashSet<&str> = HashSet::new();
+    for entry in entries {
+        keys_by_file
+            .entry(entry.source_file.as_str())
+            .or_default()
+            .insert(entry.canonical_key.as_str());
+        if matches!(entry.source_format, SourceFormat::Env) {
+            env_files.insert(entry.source_file.as_str());
+        }
+    }
+
+    let keys_of = |files: &[&str]| -> HashSet<&str> {
+        files
+            .iter()
+            .filter_map(|file| keys_by_file.get(file))
+            .flat_map(|keys| keys.iter().copied())
+            .collect()
+    };
+
+    let mut result = Vec::new();
+
+    for &env_file in &env_files {
+        if map.is_template(env_file) {
+            continue;
+        }
+        let environment = map.environment_of(env_file);
+
+        // Keys the file effectively provides: its own, those of the env files loaded before it,
+        // and those of the structured files in its environment
+        let mut provided_by: Vec<&str> = vec![env_file];
+        let earlier = load_order(env_file);
+        provided_by.extend(earlier.iter().map(|f| f.as_str()));
+        if let Some(name) = environment {
+            provided_by.extend(
+                keys_by_file
+                    .keys()
+                    .filter(|f| !env_files.contains(*f) && map.environment_of(f) == Some(name))
+                    .copied(),
+            );
+        }
+        let provided = keys_of(&provided_by);
+
+        // Expected keys come from the nearest template, or else from the peer env files: those in the
+        // same environment, or those in the same directory for files outside any environment
+        let compared_to: Vec<&str> = match map.template_for(env_file) {
+            Some(template) => vec![template],
+            None => {
+                let dir = Path::new(env_file).parent();
+                let mut peers: Vec<&str> = env_files
+                    .iter()
+                    .filter(|f| **f != env_file && !map.is_template(f))
+                    .filter(|f| match environment {
+                        Some(name) => map.environment_of(f) == Some(name),
+                        None => map.environment_of(f).is_none() && Path::new(f).parent() == dir,
+                    })
+                    .copied()
+                    .collect();
+                peers.sort();
+                peers
+            }
+        };
+        let expected = keys_of(&compared_to);
+
+        // Report missing keys as spelled in the files they were expected from
+        let mut missing: Vec<String> = expected
+            .difference(&provided)
+            .map(|canonical| {
+                entries
+                    .iter()
+                    .find(|e| e.canonical_key == *canonical && compared_to.contains(&e.source_file.as_str()))
+                    .map(|e| e.key.clone())
+                    .unwrap_or_else(|| canonical.to_string())
+            })
+            .collect();
+        missing.sort();
+
+        if !missing.is_empty() {
+            result.push(MissingKeys {
+                file: env_file.to_string(),
+                missing_keys: missing,
+                environment: environment.map(|name| name.to_string()),
+                compared_to: compared_to.iter().map(|f| f.to_string()).collect(),
+            });
+        }
+    }
+
+    result.sort_by(|a, b| a.file.cmp(&b.file));
+    result
+}
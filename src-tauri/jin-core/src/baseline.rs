@@ -0,0 +1,86 @@
+//! Baseline files: a snapshot of known issues, so checks only fail on issues introduced since.
+//!
+//! Issues are fingerprinted by code, file and key, never by message or line, so editing a file
+//! doesn't turn its known issues into new ones. The same fingerprint can be recorded more than
+//! once; each recorded copy covers one issue.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::types::Issue;
+
+/// Default baseline file name, in the project root.
+pub const BASELINE_FILE: &str = "jin-baseline.json";
+
+const BASELINE_VERSION: u32 = 1;
+
+/// What identifies an issue across scans.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Fingerprint {
+    pub code: String,
+    pub file: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+}
+
+impl Fingerprint {
+    pub fn of(issue: &Issue) -> Self {
+        Self {
+            code: issue.code.clone(),
+            file: issue.file.clone(),
+            key: issue.key.clone(),
+        }
+    }
+}
+
+/// The known issues recorded in a baseline file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub version: u32,
+    pub issues: Vec<Fingerprint>,
+}
+
+impl Baseline {
+    /// Records `issues` as known, sorted so the file diffs cleanly when refreshed.
+    pub fn from_issues(issues: &[Issue]) -> Self {
+        let mut issues: Vec<Fingerprint> = issues.iter().map(Fingerprint::of).collect();
+        issues.sort();
+        Self {
+            version: BASELINE_VERSION,
+            issues,
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read baseline {}: {}", path.display(), e))?;
+        let baseline: Baseline = serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid baseline {}: {}", path.display(), e))?;
+        if baseline.version > BASELINE_VERSION {
+            return Err(format!(
+                "Baseline {} has version {}, but this version of Jin reads up to {}",
+                path.display(),
+                baseline.version,
+                BASELINE_VERSION
+            ));
+        }
+        Ok(baseline)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("JSON serialization error: {}", e))?;
+        std::fs::write(path, json + "\n")
+            .map_err(|e| format!("Failed to write baseline {}: {}", path.display(), e))
+    }
+
+    /// How many issues each fingerprint covers.
+    pub(crate) fn counts(&self) -> HashMap<Fingerprint, usize> {
+        let mut counts = HashMap::new();
+        for fingerprint in &self.issues {
+            *counts.entry(fingerprint.clone()).or_insert(0) += 1;
+        }
+        counts
+    }
+}
@@ -0,0 +1,282 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::{DuplicateKind, ScanResult};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Off,
+    #[serde(alias = "warn")]
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IssueCategory {
+    Duplicates,
+    ValueConflicts,
+    MissingKeys,
+    ParseErrors,
+    UndefinedReferences,
+    ReferenceCycles,
+    ExposedSecrets,
+    SchemaViolations,
+    Policies,
+    UnusedKeys,
+    UndefinedKeys,
+}
+
+impl IssueCategory {
+    /// Every category, in code order.
+    pub const ALL: [IssueCategory; 11] = [
+        IssueCategory::Duplicates,
+        IssueCategory::ValueConflicts,
+        IssueCategory::MissingKeys,
+        IssueCategory::ParseErrors,
+        IssueCategory::UndefinedReferences,
+        IssueCategory::ReferenceCycles,
+        IssueCategory::ExposedSecrets,
+        IssueCategory::SchemaViolations,
+        IssueCategory::Policies,
+        IssueCategory::UnusedKeys,
+        IssueCategory::UndefinedKeys,
+    ];
+
+    /// Stable code used in reports, `jin-ignore` comments and `[[allow]]` entries.
+    pub fn code(self) -> &'static str {
+        match self {
+            IssueCategory::Duplicates => "JIN001",
+            IssueCategory::ValueConflicts => "JIN002",
+            IssueCategory::MissingKeys => "JIN003",
+            IssueCategory::ParseErrors => "JIN004",
+            IssueCategory::UndefinedReferences => "JIN005",
+            IssueCategory::ReferenceCycles => "JIN006",
+            IssueCategory::ExposedSecrets => "JIN007",
+            IssueCategory::SchemaViolations => "JIN008",
+            IssueCategory::Policies => "JIN009",
+            IssueCategory::UnusedKeys => "JIN010",
+            IssueCategory::UndefinedKeys => "JIN011",
+        }
+    }
+
+    /// Short human-readable name.
+    pub fn title(self) -> &'static str {
+        match self {
+            IssueCategory::Duplicates => "Duplicate key",
+            IssueCategory::ValueConflicts => "Value conflict",
+            IssueCategory::MissingKeys => "Missing key",
+            IssueCategory::ParseErrors => "Parse error",
+            IssueCategory::UndefinedReferences => "Undefined reference",
+            IssueCategory::ReferenceCycles => "Reference cycle",
+            IssueCategory::ExposedSecrets => "Exposed secret",
+            IssueCategory::SchemaViolations => "Schema violation",
+            IssueCategory::Policies => "Policy violation",
+            IssueCategory::UnusedKeys => "Unused key",
+            IssueCategory::UndefinedKeys => "Undefined key",
+        }
+    }
+
+    /// One-sentence explanation of what the category reports.
+    pub fn description(self) -> &'static str {
+        match self {
+            IssueCategory::Duplicates => "A key is defined with the same value in several files.",
+            IssueCategory::ValueConflicts => "A key has different values or types in different files.",
+            IssueCategory::MissingKeys => "An env file lacks a key that its template or peer files define.",
+            IssueCategory::ParseErrors => "A config file could not be parsed.",
+            IssueCategory::UndefinedReferences => "A ${VAR} reference points to a variable nothing defines.",
+            IssueCategory::ReferenceCycles => "Variables reference each other in a loop.",
+            IssueCategory::ExposedSecrets => {
+                "A secret value is in a file that git tracks or that no .gitignore rule covers."
+            }
+            IssueCategory::SchemaViolations => "A value doesn't satisfy the file's JSON Schema.",
+            IssueCategory::Policies => "A value or key name breaks a policy from jin.toml.",
+            IssueCategory::UnusedKeys => "An env file defines a key that no source file reads.",
+            IssueCategory::UndefinedKeys => "Source code reads a variable that no env file defines.",
+        }
+    }
+}
+
+/// Severity assigned to each issue category when gating a scan (`[rules]` in jin.toml).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CheckPolicy {
+    pub duplicates: Severity,
+    /// Duplicates whose values or types differ between files
+    pub value_conflicts: Severity,
+    pub missing_keys: Severity,
+    pub parse_errors: Severity,
+    pub undefined_references: Severity,
+    pub reference_cycles: Severity,
+    /// Secrets in files that are tracked by git or not gitignored
+    pub exposed_secrets: Severity,
+    /// Values that don't satisfy the file's JSON Schema
+    pub schema_violations: Severity,
+    /// Env keys no source file reads (`[code]` indexing only)
+    pub unused_keys: Severity,
+    /// Variables source code reads that no env file defines (`[code]` indexing only)
+    pub undefined_keys: Severity,
+}
+
+impl Default for CheckPolicy {
+    fn default() -> Self {
+        Self {
+            duplicates: Severity::Warning,
+            value_conflicts: Severity::Warning,
+            missing_keys: Severity::Error,
+            parse_errors: Severity::Error,
+            // The variable may still come from the process environment at runtime
+            undefined_references: Severity::Warning,
+            reference_cycles: Severity::Error,
+            exposed_secrets: Severity::Error,
+            schema_violations: Severity::Error,
+            unused_keys: Severity::Warning,
+            // The variable may be set by the deployment rather than an env file
+            undefined_keys: Severity::Warning,
+        }
+    }
+}
+
+impl CheckPolicy {
+    /// Policy violations carry their own severity, so they report as errors here.
+    pub fn severity(&self, category: IssueCategory) -> Severity {
+        match category {
+            IssueCategory::Duplicates => self.duplicates,
+            IssueCategory::ValueConflicts => self.value_conflicts,
+            IssueCategory::MissingKeys => self.missing_keys,
+            IssueCategory::ParseErrors => self.parse_errors,
+            IssueCategory::UndefinedReferences => self.undefined_references,
+            IssueCategory::ReferenceCycles => self.reference_cycles,
+            IssueCategory::ExposedSecrets => self.exposed_secrets,
+            IssueCategory::SchemaViolations => self.schema_violations,
+            IssueCategory::Policies => Severity::Error,
+            IssueCategory::UnusedKeys => self.unused_keys,
+            IssueCategory::UndefinedKeys => self.undefined_keys,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryOutcome {
+    pub category: IssueCategory,
+    pub severity: Severity,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckOutcome {
+    pub passed: bool,
+    pub errors: usize,
+    pub warnings: usize,
+    pub categories: Vec<CategoryOutcome>,
+}
+
+/// Machine-readable check output: the verdict alongside the full scan result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckReport {
+    pub outcome: CheckOutcome,
+    pub result: ScanResult,
+}
+
+pub fn evaluate(result: &ScanResult, policy: &CheckPolicy) -> CheckOutcome {
+    let issues = &result.issues;
+    let missing_count: usize = issues
+        .missing_by_env_file
+        .iter()
+        .map(|m| m.missing_keys.len())
+        .sum();
+
+    let mut categories = vec![
+        CategoryOutcome {
+            category: IssueCategory::Duplicates,
+            severity: policy.duplicates,
+            // Conflicting duplicates are counted under ValueConflicts only, as JIN002
+            count: issues
+                .duplicates
+                .iter()
+                .filter(|d| d.kind == DuplicateKind::SameValue)
+                .count(),
+        },
+        CategoryOutcome {
+            category: IssueCategory::ValueConflicts,
+            severity: policy.value_conflicts,
+            count: issues
+                .duplicates
+                .iter()
+                .filter(|d| d.kind != DuplicateKind::SameValue)
+                .count(),
+        },
+        CategoryOutcome {
+            category: IssueCategory::MissingKeys,
+            severity: policy.missing_keys,
+            count: missing_count,
+        },
+        CategoryOutcome {
+            category: IssueCategory::ParseErrors,
+            severity: policy.parse_errors,
+            count: issues.parse_errors.len(),
+        },
+        CategoryOutcome {
+            category: IssueCategory::UndefinedReferences,
+            severity: policy.undefined_references,
+            count: issues.undefined_references.len(),
+        },
+        CategoryOutcome {
+            category: IssueCategory::ReferenceCycles,
+            severity: policy.reference_cycles,
+            count: issues.reference_cycles.len(),
+        },
+        CategoryOutcome {
+            category: IssueCategory::ExposedSecrets,
+            severity: policy.exposed_secrets,
+            count: issues.exposed_secrets.iter().map(|e| e.secrets.len()).sum(),
+        },
+        CategoryOutcome {
+            category: IssueCategory::SchemaViolations,
+            severity: policy.schema_violations,
+            count: issues.schema_violations.len(),
+        },
+        CategoryOutcome {
+            category: IssueCategory::UnusedKeys,
+            severity: policy.unused_keys,
+            count: issues.unused_keys.len(),
+        },
+        CategoryOutcome {
+            category: IssueCategory::UndefinedKeys,
+            severity: policy.undefined_keys,
+            count: issues.undefined_keys.len(),
+        },
+    ];
+    // Each policy carries its own severity
+    for severity in [Severity::Error, Severity::Warning] {
+        let count = issues
+            .policy_violations
+            .iter()
+            .filter(|v| v.severity == severity)
+            .count();
+        if count > 0 {
+            categories.push(CategoryOutcome {
+                category: IssueCategory::Policies,
+                severity,
+                count,
+            });
+        }
+    }
+
+    let count_at = |severity: Severity| -> usize {
+        categories
+            .iter()
+            .filter(|c| c.severity == severity)
+            .map(|c| c.count)
+            .sum()
+    };
+    let errors = count_at(Severity::Error);
+    let warnings = count_at(Severity::Warning);
+
+    CheckOutcome {
+        passed: errors == 0,
+        errors,
+        warnings,
+        categories,
+    }
+}
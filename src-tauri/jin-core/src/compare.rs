@@ -0,0 +1,147 @@
+//! Config diffs: the keys added, removed or changed between two sets of entries, taken from two
+//! files, two environments, or two scanned projects. Secret values are masked.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::analyzer::{display_value, has_type_conflict, MASKED_VALUE};
+use crate::environments::load_order;
+use crate::types::{DuplicateValue, NormalizedEntry, ScanResult};
+
+/// The entries one side of a comparison covers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum CompareSide {
+    /// One scanned file, relative to the scan root
+    File { path: String },
+    /// The files of an environment (see `environments`)
+    Environment { name: String },
+    /// Every scanned file; two whole projects are compared file by file
+    All,
+}
+
+/// A key that differs between the two sides.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyChange {
+    /// The key as spelled on the left, or on the right when it was added
+    pub key: String,
+    pub canonical_key: String,
+    /// The file both sides were read from, when two projects are compared
+    pub file: Option<String>,
+    /// Absent for added keys; values are masked when either side is a secret
+    pub left: Option<DuplicateValue>,
+    /// Absent for removed keys
+    pub right: Option<DuplicateValue>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigDiff {
+    /// Keys only on the right
+    pub added: Vec<KeyChange>,
+    /// Keys only on the left
+    pub removed: Vec<KeyChange>,
+    pub changed_value: Vec<KeyChange>,
+    /// Keys whose values differ in type, e.g. a number on one side and text on the other
+    pub changed_type: Vec<KeyChange>,
+    pub unchanged: usize,
+}
+
+impl ConfigDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed_value.is_empty() && self.changed_type.is_empty()
+    }
+}
+
+/// Compares `left_side` of one scan with `right_side` of another (or of the same scan).
+pub fn compare(
+    left: &ScanResult,
+    left_side: &CompareSide,
+    right: &ScanResult,
+    right_side: &CompareSide,
+) -> Result<ConfigDiff, String> {
+    let by_file = *left_side == CompareSide::All && *right_side == CompareSide::All;
+    Ok(diff_entries(&select(left, left_side)?, &select(right, right_side)?, by_file))
+}
+
+/// The entries `side` covers. Where several of its files define a key, the one loaded last is
+/// listed last, so it wins in `diff_entries` (`.env.production.local` over `.env.production`).
+pub fn select(result: &ScanResult, side: &CompareSide) -> Result<Vec<NormalizedEntry>, String> {
+    let files: Vec<String> = match side {
+        CompareSide::File { path } => {
+            if !result.files.iter().any(|f| f.path == *path) {
+                return Err(format!("{} is not a scanned file", path));
+            }
+            vec![path.clone()]
+        }
+        CompareSide::Environment { name } => {
+            let environment = result.environments.iter().find(|e| e.name == *name).ok_or_else(|| {
+                let names: Vec<&str> = result.environments.iter().map(|e| e.name.as_str()).collect();
+                format!("no environment named '{}' (found: {})", name, names.join(", "))
+            })?;
+            let mut files = environment.files.clone();
+            files.sort_by_key(|file| (load_order(file).len(), file.clone()));
+            files
+        }
+        CompareSide::All => return Ok(result.entries.clone()),
+    };
+
+    Ok(files
+        .iter()
+        .flat_map(|file| result.entries.iter().filter(move |e| e.source_file == *file))
+        .cloned()
+        .collect())
+}
+
+/// Compares two sets of entries by canonical key, or by file and canonical key with `by_file`.
+/// A key defined more than once on a side keeps its last definition.
+pub fn diff_entries(left: &[NormalizedEntry], right: &[NormalizedEntry], by_file: bool) -> ConfigDiff {
+    let (left, right) = (index(left, by_file), index(right, by_file));
+    let identities: BTreeSet<&(&str, &str)> = left.keys().chain(right.keys()).collect();
+
+    let mut diff = ConfigDiff::default();
+    for identity in identities {
+        let (old, new) = (left.get(identity).copied(), right.get(identity).copied());
+        let masked = old.is_some_and(|e| e.shows_secret()) || new.is_some_and(|e| e.shows_secret());
+        let change = KeyChange {
+            key: old.or(new).map(|e| e.key.clone()).unwrap_or_default(),
+            canonical_key: identity.1.to_string(),
+            file: by_file.then(|| identity.0.to_string()),
+            left: old.map(|e| side_value(e, masked)),
+            right: new.map(|e| side_value(e, masked)),
+        };
+        match (old, new) {
+            (None, Some(_)) => diff.added.push(change),
+            (Some(_), None) => diff.removed.push(change),
+            (Some(old), Some(new)) if has_type_conflict([old, new].into_iter()) => diff.changed_type.push(change),
+            (Some(old), Some(new)) if display_value(old) != display_value(new) => diff.changed_value.push(change),
+            _ => diff.unchanged += 1,
+        }
+    }
+    diff
+}
+
+// (file when comparing by file, canonical key) -> last definition
+fn index(entries: &[NormalizedEntry], by_file: bool) -> BTreeMap<(&str, &str), &NormalizedEntry> {
+    let mut keys = BTreeMap::new();
+    for entry in entries {
+        let file = if by_file { entry.source_file.as_str() } else { "" };
+        keys.insert((file, entry.canonical_key.as_str()), entry);
+    }
+    keys
+}
+
+fn side_value(entry: &NormalizedEntry, masked: bool) -> DuplicateValue {
+    DuplicateValue {
+        file: entry.source_file.clone(),
+        key: entry.key.clone(),
+        value: if masked {
+            Some(MASKED_VALUE.to_string())
+        } else {
+            display_value(entry)
+        },
+        inferred_type: entry.inferred_type,
+        is_secret: entry.shows_secret(),
+    }
+}
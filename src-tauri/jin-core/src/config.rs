@@ -0,0 +1,201 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::check::CheckPolicy;
+use crate::diagnostics::{self, AllowRule};
+use crate::environments::EnvironmentsConfig;
+use crate::keys::KeysConfig;
+use crate::policies::{self, Policy};
+use crate::scanner::build_glob_set;
+use crate::schema::SchemasConfig;
+use crate::secrets::SecretDetector;
+use crate::types::{ExportFormat, ScanOptions};
+use crate::usage::CodeConfig;
+
+/// Project configuration file, looked up in the scan root.
+pub const CONFIG_FILE: &str = "jin.toml";
+
+/// Settings loaded from `jin.toml`. Every section is optional and falls back to Jin's defaults;
+/// unknown sections and settings are errors, so a misspelled one isn't silently ignored.
+///
+/// ```toml
+/// [scan]
+/// ignore_dirs = ["node_modules", ".git", "vendor"]
+/// exclude = ["fixtures/**"]
+/// concurrency = 8
+///
+/// [[scan.format_rules]]
+/// pattern = "config/*.conf"
+/// format = "env"
+///
+/// [watch]
+/// debounce_ms = 500
+///
+/// [secrets]
+/// keywords = ["SECRET", "PASSWORD", "TOKEN", "DSN"]
+///
+/// [[secrets.patterns]]
+/// name = "internal-token"
+/// pattern = "itk_[a-z0-9]{32}"
+///
+/// [keys]
+/// strip_prefixes = ["APP_"]
+///
+/// [environments]
+/// templates = [".env.example"]
+///
+/// [environments.groups]
+/// production = [".env.production", "config/production.yaml"]
+///
+/// [schemas]
+/// store = "schemas"
+///
+/// [[schemas.mappings]]
+/// pattern = "config/*.yaml"
+/// schema = "schemas/app.schema.json"
+///
+/// [[policies]]
+/// id = "urls"
+/// keys = ["*_URL"]
+/// format = "url"
+///
+/// [code]
+/// enabled = true
+/// exclude = ["**/*.test.ts"]
+///
+/// [[allow]]
+/// codes = ["JIN002"]
+/// keys = ["LOG_LEVEL"]
+/// reason = "Log levels differ per environment on purpose"
+///
+/// [rules]
+/// duplicates = "off"
+/// value_conflicts = "error"
+/// missing_keys = "error"
+/// undefined_references = "off"
+///
+/// [export]
+/// format = "env"
+/// output = ".env.example"
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct JinConfig {
+    pub scan: ScanOptions,
+    pub watch: WatchConfig,
+    pub secrets: SecretsConfig,
+    pub keys: KeysConfig,
+    pub environments: EnvironmentsConfig,
+    pub schemas: SchemasConfig,
+    pub policies: Vec<Policy>,
+    pub code: CodeConfig,
+    pub allow: Vec<AllowRule>,
+    pub rules: CheckPolicy,
+    pub export: ExportConfig,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WatchConfig {
+    pub debounce_ms: u64,
+}
+
+impl Default for WatchConfig {
+    fn default() -> Self {
+        Self { debounce_ms: 1000 }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SecretsConfig {
+    /// Keys containing any of these words (case-insensitive) are treated as secrets
+    pub keywords: Vec<String>,
+    /// Value patterns checked before the built-in ones
+    pub patterns: Vec<SecretPattern>,
+    /// Minimum Shannon entropy, in bits per character, for an unrecognized value to look random
+    pub entropy_threshold: f64,
+    /// Values shorter than this are never flagged on entropy alone
+    pub entropy_min_length: usize,
+}
+
+impl Default for SecretsConfig {
+    fn default() -> Self {
+        Self {
+            keywords: ["SECRET", "PASSWORD", "TOKEN", "API_KEY", "PRIVATE_KEY", "CREDENTIALS"]
+                .iter()
+                .map(|k| k.to_string())
+                .collect(),
+            patterns: Vec::new(),
+            entropy_threshold: 4.0,
+            entropy_min_length: 20,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SecretPattern {
+    pub name: String,
+    /// Regular expression matched against values; if it has a capture group, the captured text
+    /// must not be a placeholder such as `${VAR}`
+    pub pattern: String,
+    #[serde(default = "default_pattern_confidence")]
+    pub confidence: f64,
+}
+
+fn default_pattern_confidence() -> f64 {
+    0.9
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExportConfig {
+    pub format: ExportFormat,
+    /// Output path relative to the project root; defaults to a per-format file name
+    pub output: Option<String>,
+}
+
+impl Default for ExportConfig {
+    fn default() -> Self {
+        Self {
+            format: ExportFormat::Env,
+            output: None,
+        }
+    }
+}
+
+impl JinConfig {
+    /// Loads `jin.toml` from `root`, or returns the defaults when there is none.
+    pub fn discover(root: &str) -> Result<Self, String> {
+        let path = Path::new(root).join(CONFIG_FILE);
+        if path.is_file() {
+            Self::load(&path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let config: JinConfig = toml::from_str(&content)
+            .map_err(|e| format!("Invalid {}: {}", path.display(), e))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        build_glob_set(&self.scan.include)?;
+        build_glob_set(&self.scan.exclude)?;
+        let rule_patterns: Vec<String> =
+            self.scan.format_rules.iter().map(|r| r.pattern.clone()).collect();
+        build_glob_set(&rule_patterns)?;
+        SecretDetector::new(&self.secrets)?;
+        self.schemas.validate()?;
+        policies::validate(&self.policies)?;
+        self.code.validate()?;
+        diagnostics::validate(&self.allow)?;
+        self.environments.validate()
+    }
+}
@@ -0,0 +1,441 @@
+//! Every issue as one flat list with stable codes, plus the two ways to silence one: inline
+//! `jin-ignore` comments and `[[allow]]` entries in jin.toml.
+//!
+//! `# jin-ignore: JIN002` at the end of a line silences those codes on that line; on a line of its
+//! own it covers the next line. `# jin-ignore-file: JIN003` covers the whole file. Without codes,
+//! every issue is silenced. Policy violations can also be named by their policy id.
+//!
+//! Silenced issues are removed from the typed lists in `ScanIssues` too, so checks and the UI agree.
+//! Issues recorded in a baseline are removed the same way, but counted separately.
+
+use globset::{Glob, GlobMatcher, GlobSet};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use crate::baseline::{Baseline, Fingerprint};
+use crate::check::{CheckPolicy, IssueCategory, Severity};
+use crate::config::JinConfig;
+use crate::scanner::build_glob_set;
+use crate::types::{DuplicateKind, ExposureKind, Issue, NormalizedEntry, ScanIssues, Span};
+
+/// An inline `jin-ignore` comment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suppression {
+    /// The line it covers; `None` for the whole file
+    pub line: Option<usize>,
+    /// Codes or policy ids; empty means every issue
+    pub codes: Vec<String>,
+}
+
+/// `[[allow]]` in jin.toml: issues matching every selector that is set are silenced.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AllowRule {
+    /// Codes or policy ids
+    pub codes: Vec<String>,
+    /// Globs over file paths
+    pub files: Vec<String>,
+    /// Globs over key names
+    pub keys: Vec<String>,
+    /// Why the issue is acceptable, for reviewers
+    pub reason: Option<String>,
+}
+
+/// The flat issue list, and how many issues were left out of it.
+#[derive(Debug, Clone, Default)]
+pub struct IssueList {
+    pub issues: Vec<Issue>,
+    /// Silenced by `jin-ignore` comments or `[[allow]]` entries
+    pub suppressed: usize,
+    /// Already recorded in the baseline
+    pub baselined: usize,
+}
+
+/// Checks that every allow rule compiles and selects something; called when jin.toml is loaded.
+pub(crate) fn validate(rules: &[AllowRule]) -> Result<(), String> {
+    for rule in rules {
+        CompiledAllow::new(rule)?;
+    }
+    Ok(())
+}
+
+/// Finds the `jin-ignore` comments in a file.
+pub fn suppressions(content: &str) -> Vec<Suppression> {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    let pattern = PATTERN.get_or_init(|| {
+        Regex::new(r"(?:#|//)\s*jin-ignore(-file)?(?:\s*:\s*([A-Za-z0-9_.,\s-]*))?\s*$")
+            .expect("suppression pattern")
+    });
+
+    let lines: Vec<&str> = content.lines().collect();
+    let mut found = Vec::new();
+    for (idx, line) in lines.iter().enumerate() {
+        let Some(captures) = pattern.captures(line) else {
+            continue;
+        };
+        let codes: Vec<String> = captures
+            .get(2)
+            .map(|m| m.as_str())
+            .unwrap_or_default()
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|code| !code.is_empty())
+            .map(|code| code.to_string())
+            .collect();
+
+        let line = if captures.get(1).is_some() {
+            None
+        } else if line[..captures.get(0).map(|m| m.start()).unwrap_or(0)].trim().is_empty() {
+            // A comment on its own line covers the next line with content
+            (idx + 1..lines.len())
+                .find(|&next| {
+                    let text = lines[next].trim();
+                    !text.is_empty() && !text.starts_with('#')
+                })
+                .map(|next| next + 1)
+        } else {
+            Some(idx + 1)
+        };
+        if line.is_none() && captures.get(1).is_none() {
+            continue;
+        }
+        found.push(Suppression { line, codes });
+    }
+    found
+}
+
+/// Builds the flat issue list from `issues`, dropping silenced and baselined issues from both.
+pub fn apply(
+    issues: &mut ScanIssues,
+    entries: &[NormalizedEntry],
+    config: &JinConfig,
+    suppressions: &HashMap<String, Vec<Suppression>>,
+    baseline: Option<&Baseline>,
+) -> IssueList {
+    let mut spans: HashMap<(&str, &str), Span> = HashMap::new();
+    for entry in entries {
+        if let Some(span) = entry.span {
+            spans.insert((entry.source_file.as_str(), entry.canonical_key.as_str()), span);
+            spans.insert((entry.source_file.as_str(), entry.key.as_str()), span);
+        }
+    }
+    let span_of = |file: &str, key: &str| spans.get(&(file, key)).copied();
+
+    let mut collector = Collector {
+        // Allow rules were validated when the config was loaded
+        allow: config.allow.iter().filter_map(|r| CompiledAllow::new(r).ok()).collect(),
+        suppressions,
+        rules: &config.rules,
+        baseline: baseline.map(Baseline::counts).unwrap_or_default(),
+        list: IssueList::default(),
+    };
+
+    issues.duplicates.retain(|duplicate| {
+        let (category, message) = match duplicate.kind {
+            DuplicateKind::SameValue => (
+                IssueCategory::Duplicates,
+                format!("{} has the same value in {}", duplicate.key, duplicate.files.join(", ")),
+            ),
+            DuplicateKind::DifferentValues => (
+                IssueCategory::ValueConflicts,
+                format!("{} has different values in {}", duplicate.key, duplicate.files.join(", ")),
+            ),
+            DuplicateKind::DifferentTypes => (
+                IssueCategory::ValueConflicts,
+                format!("{} has different types in {}", duplicate.key, duplicate.files.join(", ")),
+            ),
+        };
+        let first = duplicate.files.first().cloned().unwrap_or_default();
+        let locations: Vec<(&str, Option<Span>)> = duplicate
+            .files
+            .iter()
+            .map(|file| (file.as_str(), span_of(file, &duplicate.canonical_key)))
+            .collect();
+        collector.report(
+            Issue::new(category, collector.severity(category), message, &first)
+                .with_key(&duplicate.key)
+                .with_span(span_of(&first, &duplicate.canonical_key)),
+            &locations,
+            None,
+        )
+    });
+
+    for missing in issues.missing_by_env_file.iter_mut() {
+        let file = missing.file.clone();
+        let compared_to = missing.compared_to.join(", ");
+        missing.missing_keys.retain(|key| {
+            let category = IssueCategory::MissingKeys;
+            collector.report(
+                Issue::new(
+                    category,
+                    collector.severity(category),
+                    format!("{} is missing (expected from {})", key, compared_to),
+                    &file,
+                )
+                .with_key(key)
+                .with_fix(format!("Add {} to {}", key, file)),
+                &[],
+                None,
+            )
+        });
+    }
+    issues.missing_by_env_file.retain(|m| !m.missing_keys.is_empty());
+
+    issues.parse_errors.retain(|error| {
+        let category = IssueCategory::ParseErrors;
+        collector.report(
+            Issue::new(category, collector.severity(category), error.message.clone(), &error.file)
+                .with_span(error.span),
+            &[],
+            None,
+        )
+    });
+
+    issues.undefined_references.retain(|reference| {
+        let category = IssueCategory::UndefinedReferences;
+        collector.report(
+            Issue::new(
+                category,
+                collector.severity(category),
+                format!("{} references ${{{}}}, which is not defined", reference.key, reference.reference),
+                &reference.file,
+            )
+            .with_key(&reference.key)
+            .with_span(reference.span)
+            .with_fix(format!(
+                "Define {} or give it a default: ${{{}:-value}}",
+                reference.reference, reference.reference
+            )),
+            &[],
+            None,
+        )
+    });
+
+    issues.reference_cycles.retain(|cycle| {
+        let category = IssueCategory::ReferenceCycles;
+        let mut keys = cycle.keys.clone();
+        keys.extend(cycle.keys.first().cloned());
+        let first = cycle.keys.first().cloned().unwrap_or_default();
+        let locations: Vec<(&str, Option<Span>)> = cycle
+            .keys
+            .iter()
+            .map(|key| (cycle.file.as_str(), span_of(&cycle.file, key)))
+            .collect();
+        collector.report(
+            Issue::new(
+                category,
+                collector.severity(category),
+                format!("{} reference each other in a loop", keys.join(" -> ")),
+                &cycle.file,
+            )
+            .with_key(&first)
+            .with_span(span_of(&cycle.file, &first)),
+            &locations,
+            None,
+        )
+    });
+
+    for exposure in issues.exposed_secrets.iter_mut() {
+        let file = exposure.file.clone();
+        let (place, fix) = match exposure.kind {
+            ExposureKind::Tracked => (
+                "a file tracked by git",
+                format!("Run `git rm --cached {}`, ignore the file and rotate the secret", file),
+            ),
+            ExposureKind::NotIgnored => ("a file not covered by .gitignore", format!("Add {} to .gitignore", file)),
+        };
+        exposure.secrets.retain(|secret| {
+            let category = IssueCategory::ExposedSecrets;
+            let rule = secret.rule.as_deref().map(|r| format!(" ({})", r)).unwrap_or_default();
+            collector.report(
+                Issue::new(
+                    category,
+                    collector.severity(category),
+                    format!("{} holds a secret{} in {}", secret.key, rule, place),
+                    &file,
+                )
+                .with_key(&secret.key)
+                .with_span(secret.span)
+                .with_fix(fix.clone()),
+                &[],
+                None,
+            )
+        });
+    }
+    issues.exposed_secrets.retain(|e| !e.secrets.is_empty());
+
+    issues.schema_violations.retain(|violation| {
+        let category = IssueCategory::SchemaViolations;
+        let message = if violation.path.is_empty() {
+            violation.message.clone()
+        } else {
+            format!("{}: {}", violation.path, violation.message)
+        };
+        let mut issue = Issue::new(category, collector.severity(category), message, &violation.file)
+            .with_span(violation.span);
+        if !violation.path.is_empty() {
+            issue = issue.with_key(&violation.path);
+        }
+        collector.report(issue, &[], None)
+    });
+
+    issues.policy_violations.retain(|violation| {
+        collector.report(
+            Issue::new(
+                IssueCategory::Policies,
+                violation.severity,
+                violation.message.clone(),
+                &violation.file,
+            )
+            .with_key(&violation.key)
+            .with_span(violation.span),
+            &[],
+            Some(&violation.policy),
+        )
+    });
+
+    issues.unused_keys.retain(|unused| {
+        let category = IssueCategory::UnusedKeys;
+        collector.report(
+            Issue::new(
+                category,
+                collector.severity(category),
+                format!("{} is never read by the indexed source code", unused.key),
+                &unused.file,
+            )
+            .with_key(&unused.key)
+            .with_span(unused.span)
+            .with_fix(format!("Remove {} from {}", unused.key, unused.file)),
+            &[],
+            None,
+        )
+    });
+
+    issues.undefined_keys.retain(|undefined| {
+        let category = IssueCategory::UndefinedKeys;
+        collector.report(
+            Issue::new(
+                category,
+                collector.severity(category),
+                format!("{} is read here but no env file defines it", undefined.key),
+                &undefined.file,
+            )
+            .with_key(&undefined.key)
+            .with_span(undefined.span)
+            .with_fix(format!("Add {} to your env files and their template", undefined.key)),
+            &[],
+            None,
+        )
+    });
+
+    collector.list
+}
+
+struct Collector<'a> {
+    allow: Vec<CompiledAllow>,
+    suppressions: &'a HashMap<String, Vec<Suppression>>,
+    rules: &'a CheckPolicy,
+    // Baselined issues each fingerprint still covers
+    baseline: HashMap<Fingerprint, usize>,
+    list: IssueList,
+}
+
+impl Collector<'_> {
+    fn severity(&self, category: IssueCategory) -> Severity {
+        self.rules.severity(category)
+    }
+
+    // Records `issue` unless it is silenced, at its own location or any of `locations`, or
+    // baselined; returns whether the typed issue it came from should be kept
+    fn report(&mut self, issue: Issue, locations: &[(&str, Option<Span>)], alias: Option<&str>) -> bool {
+        let names_issue = |code: &String| {
+            code.eq_ignore_ascii_case(&issue.code) || alias.map(|a| code == a).unwrap_or(false)
+        };
+        let silenced_at = |file: &str, span: Option<Span>| {
+            self.suppressions.get(file).into_iter().flatten().any(|s| {
+                let line_matches = match s.line {
+                    None => true,
+                    Some(line) => span.map(|span| span.line == line).unwrap_or(false),
+                };
+                line_matches && (s.codes.is_empty() || s.codes.iter().any(names_issue))
+            })
+        };
+
+        let silenced = silenced_at(&issue.file, issue.span)
+            || locations.iter().any(|(file, span)| silenced_at(file, *span))
+            || self.allow.iter().any(|rule| rule.allows(&issue, alias));
+        if silenced {
+            self.list.suppressed += 1;
+            return false;
+        }
+        if issue.severity == Severity::Off {
+            return true;
+        }
+        if let Some(remaining) = self.baseline.get_mut(&Fingerprint::of(&issue)) {
+            if *remaining > 0 {
+                *remaining -= 1;
+                self.list.baselined += 1;
+                return false;
+            }
+        }
+        self.list.issues.push(issue);
+        true
+    }
+}
+
+struct CompiledAllow {
+    codes: Vec<String>,
+    files: Option<GlobSet>,
+    keys: Vec<GlobMatcher>,
+}
+
+impl CompiledAllow {
+    fn new(rule: &AllowRule) -> Result<Self, String> {
+        if rule.codes.is_empty() && rule.files.is_empty() && rule.keys.is_empty() {
+            return Err("allow: each entry needs codes, files or keys".to_string());
+        }
+        let files = if rule.files.is_empty() {
+            None
+        } else {
+            Some(build_glob_set(&rule.files).map_err(|e| format!("allow: {}", e))?)
+        };
+        let keys = rule
+            .keys
+            .iter()
+            .map(|pattern| {
+                Glob::new(pattern)
+                    .map(|glob| glob.compile_matcher())
+                    .map_err(|e| format!("allow: invalid key glob '{}': {}", pattern, e))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(Self {
+            codes: rule.codes.clone(),
+            files,
+            keys,
+        })
+    }
+
+    fn allows(&self, issue: &Issue, alias: Option<&str>) -> bool {
+        let code_matches = self.codes.is_empty()
+            || self
+                .codes
+                .iter()
+                .any(|code| code.eq_ignore_ascii_case(&issue.code) || Some(code.as_str()) == alias);
+        let file_matches = self
+            .files
+            .as_ref()
+            .map(|files| files.is_match(Path::new(&issue.file)))
+            .unwrap_or(true);
+        let key_matches = self.keys.is_empty()
+            || issue
+                .key
+                .as_ref()
+                .map(|key| self.keys.iter().any(|glob| glob.is_match(key)))
+                .unwrap_or(false);
+
+        code_matches && file_matches && key_matches
+    }
+}
@@ -0,0 +1,337 @@
+//! Parser for the de-facto dotenv grammar shared by dotenv and docker-compose:
+//!
+//! ```text
+//! # full-line comment
+//! export KEY=value            # `export` prefix is optional
+//! KEY = unquoted value        # inline comments need whitespace before `#`
+//! KEY='literal $value\n'      # single quotes: no escapes, may span lines
+//! KEY="line\nbreak"           # double quotes: \n \r \t \" \\ escapes, may span lines
+//! ```
+
+use std::ops::Range;
+
+use crate::types::Span;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quote {
+    None,
+    Single,
+    Double,
+    Backtick,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DotenvEntry {
+    pub key: String,
+    pub value: String,
+    pub quote: Quote,
+    /// Position of the key (or of `export`, when present)
+    pub span: Span,
+    /// Byte range of the key name, after any `export` prefix
+    pub key_range: Range<usize>,
+    /// Byte range of the value as written, quotes included
+    pub value_range: Range<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DotenvError {
+    pub message: String,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DotenvFile {
+    pub entries: Vec<DotenvEntry>,
+    pub errors: Vec<DotenvError>,
+}
+
+/// Parses dotenv content. Malformed lines are reported in `errors` and parsing resumes on the next line.
+/// Byte ranges are offsets into `content` after any byte order mark.
+pub fn parse(content: &str) -> DotenvFile {
+    let mut cursor = Cursor::new(content.strip_prefix('\u{feff}').unwrap_or(content));
+    let mut file = DotenvFile::default();
+
+    loop {
+        cursor.skip_blanks();
+        match cursor.peek() {
+            None => break,
+            Some('\n') | Some('\r') => {
+                cursor.bump();
+            }
+            Some('#') => cursor.skip_line(),
+            Some(_) => {
+                let line_start = cursor.clone();
+                match parse_assignment(&mut cursor) {
+                    Ok(entry) => file.entries.push(entry),
+                    Err(error) => {
+                        file.errors.push(error);
+                        // Resume right after the offending line, even if an unterminated quote ran to EOF
+                        cursor = line_start;
+                        cursor.skip_line();
+                    }
+                }
+            }
+        }
+    }
+
+    file
+}
+
+/// Writes `value` so that `parse` reads it back unchanged and nothing expands it: bare when it can
+/// be, single-quoted when it holds a `$`, double-quoted otherwise. Fails when it holds both `$` and `'`.
+pub fn format_value(value: &str) -> Result<String, String> {
+    // Bare and double-quoted values expand `$VAR`, in the scanner and in dotenv loaders alike
+    if value.contains('$') {
+        if value.contains('\'') {
+            return Err("a value with both $ and ' can't be written literally".to_string());
+        }
+        return Ok(format!("'{}'", value));
+    }
+    let needs_quotes = value != value.trim()
+        || value
+            .chars()
+            .any(|c| matches!(c, '#' | '"' | '\'' | '`' | '\\' | '\n' | '\r' | '\t'));
+    if needs_quotes {
+        Ok(double_quoted(value))
+    } else {
+        Ok(value.to_string())
+    }
+}
+
+/// Writes `value` as a double-quoted string, escaping what `parse` unescapes.
+pub fn double_quoted(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn parse_assignment(cursor: &mut Cursor) -> Result<DotenvEntry, DotenvError> {
+    let span = cursor.span();
+
+    let mut key_start = cursor.offset;
+    let mut key = cursor.take_while(is_key_char);
+    if key == "export" && matches!(cursor.peek(), Some(' ' | '\t')) {
+        let after_export = cursor.clone();
+        cursor.skip_blanks();
+        if cursor.peek().map(is_key_char).unwrap_or(false) {
+            key_start = cursor.offset;
+            key = cursor.take_while(is_key_char);
+        } else {
+            *cursor = after_export;
+        }
+    }
+    let key_range = key_start..cursor.offset;
+
+    if key.is_empty() {
+        let found = cursor.peek().unwrap_or(' ');
+        return Err(cursor.error(format!("Invalid key: expected a name before '{}'", found)));
+    }
+
+    cursor.skip_blanks();
+    match cursor.peek() {
+        Some('=') => {
+            cursor.bump();
+        }
+        None | Some('\n') | Some('\r') | Some('#') => {
+            return Err(DotenvError {
+                message: format!("Missing '=' after key '{}'", key),
+                span,
+            });
+        }
+        Some(c) => {
+            return Err(cursor.error(format!("Invalid character '{}' in key '{}'", c, key)));
+        }
+    }
+
+    let had_blank = cursor.skip_blanks();
+    let value_start = cursor.offset;
+    let (value, quote) = match cursor.peek() {
+        Some('"') => (read_quoted(cursor, '"', true)?, Quote::Double),
+        Some('\'') => (read_quoted(cursor, '\'', false)?, Quote::Single),
+        Some('`') => (read_quoted(cursor, '`', false)?, Quote::Backtick),
+        _ => (read_unquoted(cursor, had_blank), Quote::None),
+    };
+    // Unquoted values end before any trailing blanks or comment
+    let value_range = match quote {
+        Quote::None => value_start..value_start + value.len(),
+        _ => value_start..cursor.offset,
+    };
+
+    // Only whitespace or a comment may follow a value
+    cursor.skip_blanks();
+    match cursor.peek() {
+        None | Some('\n') | Some('\r') => {}
+        Some('#') => cursor.skip_line(),
+        Some(c) => {
+            return Err(cursor.error(format!("Unexpected '{}' after quoted value of '{}'", c, key)));
+        }
+    }
+
+    Ok(DotenvEntry {
+        key,
+        value,
+        quote,
+        span,
+        key_range,
+        value_range,
+    })
+}
+
+fn read_quoted(cursor: &mut Cursor, quote: char, escapes: bool) -> Result<String, DotenvError> {
+    let start = cursor.span();
+    cursor.bump();
+
+    let mut value = String::new();
+    loop {
+        match cursor.bump() {
+            None => {
+                return Err(DotenvError {
+                    message: format!("Unterminated {} quote", quote_name(quote)),
+                    span: start,
+                });
+            }
+            Some(c) if c == quote => return Ok(value),
+            Some('\\') if escapes => match cursor.bump() {
+                Some('n') => value.push('\n'),
+                Some('r') => value.push('\r'),
+                Some('t') => value.push('\t'),
+                Some('"') => value.push('"'),
+                Some('\\') => value.push('\\'),
+                // Unknown escapes are kept verbatim
+                Some(other) => {
+                    value.push('\\');
+                    value.push(other);
+                }
+                None => {
+                    return Err(DotenvError {
+                        message: format!("Unterminated {} quote", quote_name(quote)),
+                        span: start,
+                    });
+                }
+            },
+            // CRLF files: keep multi-line values as plain \n
+            Some('\r') if cursor.peek() == Some('\n') => {}
+            Some(c) => value.push(c),
+        }
+    }
+}
+
+fn read_unquoted(cursor: &mut Cursor, had_blank: bool) -> String {
+    let mut value = String::new();
+    let mut prev_blank = had_blank;
+
+    while let Some(c) = cursor.peek() {
+        if c == '\n' || c == '\r' || (c == '#' && prev_blank) {
+            break;
+        }
+        prev_blank = c == ' ' || c == '\t';
+        value.push(c);
+        cursor.bump();
+    }
+
+    value.trim_end().to_string()
+}
+
+fn quote_name(quote: char) -> &'static str {
+    match quote {
+        '"' => "double",
+        '\'' => "single",
+        _ => "backtick",
+    }
+}
+
+fn is_key_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '.' || c == '-'
+}
+
+#[derive(Clone)]
+struct Cursor<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+    column: usize,
+    /// Byte offset of the next character
+    offset: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(content: &'a str) -> Self {
+        Self {
+            chars: content.chars().peekable(),
+            line: 1,
+            column: 1,
+            offset: 0,
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        self.offset += c.len_utf8();
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn span(&self) -> Span {
+        Span {
+            line: self.line,
+            column: self.column,
+        }
+    }
+
+    fn error(&self, message: String) -> DotenvError {
+        DotenvError {
+            message,
+            span: self.span(),
+        }
+    }
+
+    /// Skips spaces and tabs, returning whether any were skipped.
+    fn skip_blanks(&mut self) -> bool {
+        let mut skipped = false;
+        while matches!(self.peek(), Some(' ' | '\t')) {
+            self.bump();
+            skipped = true;
+        }
+        skipped
+    }
+
+    /// Skips to the start of the next line.
+    fn skip_line(&mut self) {
+        while let Some(c) = self.bump() {
+            if c == '\n' {
+                break;
+            }
+        }
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> String {
+        let mut taken = String::new();
+        while let Some(c) = self.peek() {
+            if !predicate(c) {
+                break;
+            }
+            taken.push(c);
+            self.bump();
+        }
+        taken
+    }
+}
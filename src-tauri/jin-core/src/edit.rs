@@ -0,0 +1,296 @@
+//! Format-preserving edits: set, rename or delete one flattened key (the dotted paths entries
+//! carry) while leaving comments, key order, quoting and indentation of everything else intact.
+//!
+//! Every edit is checked by parsing the result again: if any key other than the edited one would
+//! change, the edit is refused rather than written.
+
+pub(crate) mod dotenv;
+mod json;
+mod toml;
+mod yaml;
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::parser::parse_content;
+use crate::secrets::SecretDetector;
+use crate::textdiff::unified_diff;
+use crate::types::SourceFormat;
+
+/// One change to a flattened key. Renames and deletes of a table path (`db`) apply to every key below it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "camelCase")]
+pub enum KeyEdit {
+    Set { key: String, value: serde_json::Value },
+    Rename { key: String, to: String },
+    Delete { key: String },
+}
+
+impl KeyEdit {
+    pub fn key(&self) -> &str {
+        match self {
+            KeyEdit::Set { key, .. } | KeyEdit::Rename { key, .. } | KeyEdit::Delete { key } => key,
+        }
+    }
+}
+
+/// The planned change to one file, previewed before it is written.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileEdit {
+    pub file: String,
+    /// File content when the edit was planned; `apply` refuses to write if it has changed since
+    pub original: String,
+    pub updated: String,
+    /// Unified diff from `original` to `updated`
+    pub diff: String,
+}
+
+/// Applies `edits` in order to `content`, returning the new content.
+pub fn edit_content(content: &str, format: &SourceFormat, edits: &[KeyEdit]) -> Result<String, String> {
+    let mut content = content.to_string();
+    for edit in edits {
+        content = edit_once(&content, format, edit)?;
+    }
+    Ok(content)
+}
+
+/// Reads `file` under `root` and plans `edits` on it. Nothing is written.
+pub fn plan(root: &str, file: &str, format: &SourceFormat, edits: &[KeyEdit]) -> Result<FileEdit, String> {
+    let original = std::fs::read_to_string(Path::new(root).join(file))
+        .map_err(|e| format!("Failed to read {}: {}", file, e))?;
+    let updated = edit_content(&original, format, edits).map_err(|e| format!("{}: {}", file, e))?;
+    Ok(FileEdit {
+        file: file.to_string(),
+        diff: unified_diff(file, &original, &updated),
+        original,
+        updated,
+    })
+}
+
+/// Writes planned edits under `root`. Fails before writing anything if a file changed since it was
+/// planned or any new content can't be written.
+pub fn apply(root: &str, edits: &[FileEdit]) -> Result<(), String> {
+    write_checked(
+        root,
+        edits
+            .iter()
+            .map(|edit| (edit.file.as_str(), edit.original.as_str(), edit.updated.as_str())),
+    )
+}
+
+// (file, content it was planned from, new content). Every file is first written to a temporary
+// sibling and only then renamed into place, so a failed write leaves the originals untouched.
+pub(crate) fn write_checked<'a>(
+    root: &str,
+    changes: impl Iterator<Item = (&'a str, &'a str, &'a str)> + Clone,
+) -> Result<(), String> {
+    for (file, original, _) in changes.clone() {
+        let current = std::fs::read_to_string(Path::new(root).join(file))
+            .map_err(|e| format!("Failed to read {}: {}", file, e))?;
+        if current != original {
+            return Err(format!("{} changed since the preview; scan again and retry", file));
+        }
+    }
+
+    let mut staged: Vec<(&str, PathBuf, PathBuf)> = Vec::new();
+    for (file, _, updated) in changes {
+        let path = Path::new(root).join(file);
+        let temp = temp_sibling(&path);
+        if let Err(e) = stage(&path, &temp, updated) {
+            let _ = std::fs::remove_file(&temp);
+            discard(&staged);
+            return Err(format!("Failed to write {}: {}; nothing was written", file, e));
+        }
+        staged.push((file, path, temp));
+    }
+
+    for (index, (file, path, temp)) in staged.iter().enumerate() {
+        if let Err(e) = std::fs::rename(temp, path) {
+            discard(&staged[index..]);
+            let written: Vec<&str> = staged[..index].iter().map(|(file, _, _)| *file).collect();
+            let written = if written.is_empty() {
+                "nothing was written".to_string()
+            } else {
+                format!("already written: {}", written.join(", "))
+            };
+            return Err(format!("Failed to replace {}: {}; {}", file, e, written));
+        }
+    }
+    Ok(())
+}
+
+// `.env` -> `.env.jin-<pid>.tmp` in the same directory, so the rename stays on one file system
+fn temp_sibling(path: &Path) -> PathBuf {
+    let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    path.with_file_name(format!("{}.jin-{}.tmp", name, std::process::id()))
+}
+
+// Writes the new content next to the file, with the file's permissions (`.env` files are often 0600)
+fn stage(path: &Path, temp: &Path, content: &str) -> std::io::Result<()> {
+    std::fs::write(temp, content)?;
+    std::fs::set_permissions(temp, std::fs::metadata(path)?.permissions())
+}
+
+fn discard(staged: &[(&str, PathBuf, PathBuf)]) {
+    for (_, _, temp) in staged {
+        let _ = std::fs::remove_file(temp);
+    }
+}
+
+fn edit_once(content: &str, format: &SourceFormat, edit: &KeyEdit) -> Result<String, String> {
+    let before = flatten(content, format)?;
+    let expected = expected_keys(&before, format, edit)?;
+
+    let updated = match format {
+        SourceFormat::Env => dotenv::edit(content, edit),
+        SourceFormat::Json => json::edit(content, edit),
+        SourceFormat::Yaml => yaml::edit(content, edit),
+        SourceFormat::Toml => toml::edit(content, edit),
+    }?;
+
+    if flatten(&updated, format).ok().as_ref() != Some(&expected) {
+        return Err(format!(
+            "can't edit '{}' here without changing other keys; edit the file by hand",
+            edit.key()
+        ));
+    }
+    Ok(updated)
+}
+
+// Flattened keys and values, as the scanner reads them
+fn flatten(content: &str, format: &SourceFormat) -> Result<BTreeMap<String, serde_json::Value>, String> {
+    let parsed = parse_content(content, "", format, &SecretDetector::default());
+    if *format != SourceFormat::Env {
+        if let Some(error) = parsed.errors.first() {
+            return Err(error.message.clone());
+        }
+    }
+    Ok(parsed
+        .entries
+        .into_iter()
+        .map(|entry| (entry.key, entry.value.unwrap_or(serde_json::Value::Null)))
+        .collect())
+}
+
+// The keys the file should hold after `edit`, or why the edit can't be made
+fn expected_keys(
+    before: &BTreeMap<String, serde_json::Value>,
+    format: &SourceFormat,
+    edit: &KeyEdit,
+) -> Result<BTreeMap<String, serde_json::Value>, String> {
+    let nested = *format != SourceFormat::Env;
+    // The key itself, plus the keys below it when it names a table
+    let covered = |path: &str| -> Vec<String> {
+        before
+            .keys()
+            .filter(|k| *k == path || (nested && is_below(k, path)))
+            .cloned()
+            .collect()
+    };
+
+    let mut expected = before.clone();
+    match edit {
+        KeyEdit::Set { key, value } => {
+            if value.is_array() || value.is_object() {
+                return Err(format!("'{}': only strings, numbers, booleans and null can be set", key));
+            }
+            if nested {
+                if before.keys().any(|k| is_below(k, key)) {
+                    return Err(format!("'{}' holds nested keys; set those instead", key));
+                }
+                if let Some(parent) = before.keys().find(|k| is_below(key, k)) {
+                    return Err(format!("'{}' is a value, so '{}' can't be added below it", parent, key));
+                }
+            }
+            let value = match format {
+                SourceFormat::Env => serde_json::Value::String(env_value(value)),
+                _ => value.clone(),
+            };
+            expected.insert(key.clone(), value);
+        }
+        KeyEdit::Rename { key, to } => {
+            let moved = covered(key);
+            if moved.is_empty() {
+                return Err(format!("'{}' not found", key));
+            }
+            if to == key || is_below(to, key) {
+                return Err(format!("can't rename '{}' to '{}'", key, to));
+            }
+            if !covered(to).is_empty() || (nested && before.keys().any(|k| is_below(to, k))) {
+                return Err(format!("'{}' already exists", to));
+            }
+            for old in moved {
+                let value = expected.remove(&old).unwrap_or_default();
+                expected.insert(format!("{}{}", to, &old[key.len()..]), value);
+            }
+        }
+        KeyEdit::Delete { key } => {
+            let removed = covered(key);
+            if removed.is_empty() {
+                return Err(format!("'{}' not found", key));
+            }
+            for old in removed {
+                expected.remove(&old);
+            }
+        }
+    }
+    Ok(expected)
+}
+
+pub(crate) fn is_below(key: &str, parent: &str) -> bool {
+    key.len() > parent.len() && key.starts_with(parent) && key.as_bytes()[parent.len()] == b'.'
+}
+
+// Env files hold strings only
+fn env_value(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+// Parent path and last segment of a dotted key
+fn split_key(key: &str) -> (&str, &str) {
+    match key.rfind('.') {
+        Some(dot) => (&key[..dot], &key[dot + 1..]),
+        None => ("", key),
+    }
+}
+
+fn line_start(content: &str, offset: usize) -> usize {
+    content[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0)
+}
+
+// Offset just past the newline ending the line that holds `offset`
+fn next_line_start(content: &str, offset: usize) -> usize {
+    content[offset..]
+        .find('\n')
+        .map(|i| offset + i + 1)
+        .unwrap_or(content.len())
+}
+
+// Leading whitespace of the line that holds `offset`
+fn indent_at(content: &str, offset: usize) -> &str {
+    let start = line_start(content, offset);
+    let line = &content[start..];
+    &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+}
+
+fn newline_of(content: &str) -> &'static str {
+    if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    }
+}
+
+fn replace_range(content: &str, range: std::ops::Range<usize>, text: &str) -> String {
+    let mut updated = String::with_capacity(content.len() + text.len());
+    updated.push_str(&content[..range.start]);
+    updated.push_str(text);
+    updated.push_str(&content[range.end..]);
+    updated
+}
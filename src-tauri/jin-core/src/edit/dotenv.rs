@@ -0,0 +1,75 @@
+use super::{env_value, newline_of, next_line_start, replace_range, KeyEdit};
+use crate::dotenv::{self, double_quoted, format_value, DotenvEntry, Quote};
+
+const BOM: &str = "\u{feff}";
+
+pub(super) fn edit(content: &str, edit: &KeyEdit) -> Result<String, String> {
+    // Entry ranges are relative to the content after a byte order mark
+    let (bom, body) = match content.strip_prefix(BOM) {
+        Some(body) => (BOM, body),
+        None => ("", content),
+    };
+    let entries: Vec<DotenvEntry> = dotenv::parse(body)
+        .entries
+        .into_iter()
+        .filter(|entry| entry.key == edit.key())
+        .collect();
+
+    let updated = match edit {
+        KeyEdit::Set { key, value } => {
+            let value = env_value(value);
+            match entries.last() {
+                // The last definition is the one that counts
+                Some(entry) => {
+                    let written = quote_like(&value, entry.quote).map_err(|e| format!("'{}': {}", key, e))?;
+                    replace_range(body, entry.value_range.clone(), &written)
+                }
+                None => {
+                    let written = format_value(&value).map_err(|e| format!("'{}': {}", key, e))?;
+                    append(body, &[(key.as_str(), written.as_str())])
+                }
+            }
+        }
+        KeyEdit::Rename { to, .. } => {
+            let mut updated = body.to_string();
+            for entry in entries.iter().rev() {
+                updated = replace_range(&updated, entry.key_range.clone(), to);
+            }
+            updated
+        }
+        KeyEdit::Delete { .. } => {
+            let mut updated = body.to_string();
+            for entry in entries.iter().rev() {
+                let start = super::line_start(&updated, entry.key_range.start);
+                let end = next_line_start(&updated, entry.value_range.end);
+                updated.replace_range(start..end, "");
+            }
+            updated
+        }
+    };
+    Ok(format!("{}{}", bom, updated))
+}
+
+/// Appends one `KEY=value` line per assignment, using the file's line endings. Values are written
+/// as given, so they must already be quoted (see `dotenv::format_value`).
+pub(crate) fn append(content: &str, assignments: &[(&str, &str)]) -> String {
+    let newline = newline_of(content);
+    let mut updated = content.to_string();
+    if !updated.is_empty() && !updated.ends_with('\n') {
+        updated.push_str(newline);
+    }
+    for (key, value) in assignments {
+        updated.push_str(&format!("{}={}{}", key, value, newline));
+    }
+    updated
+}
+
+// Keeps the quoting the value was written with, when it can hold the new value literally
+fn quote_like(value: &str, quote: Quote) -> Result<String, String> {
+    match quote {
+        Quote::Double if !value.contains('$') => Ok(double_quoted(value)),
+        Quote::Single if !value.contains('\'') => Ok(format!("'{}'", value)),
+        Quote::Backtick if !value.contains('`') => Ok(format!("`{}`", value)),
+        _ => format_value(value),
+    }
+}
@@ -0,0 +1,125 @@
+use super::{indent_at, newline_of, replace_range, split_key, KeyEdit};
+use crate::locate::{json_layout, JsonLayout, JsonObject};
+
+pub(super) fn edit(content: &str, edit: &KeyEdit) -> Result<String, String> {
+    let layout = json_layout(content);
+    match edit {
+        KeyEdit::Set { key, value } => {
+            let text = serde_json::to_string(value).map_err(|e| e.to_string())?;
+            match layout.members.get(key) {
+                Some(member) => Ok(replace_range(content, member.value.clone(), &text)),
+                None => insert(content, &layout, key, &text),
+            }
+        }
+        KeyEdit::Rename { key, to } => {
+            let member = layout.members.get(key).ok_or_else(|| format!("'{}' not found", key))?;
+            let (parent, _) = parent_of(&layout, key).ok_or_else(|| format!("'{}' not found", key))?;
+            let (new_parent, name) = split_key(to);
+            if new_parent == parent {
+                // Same object: only the key changes
+                Ok(replace_range(content, member.key.clone(), &quoted(name)))
+            } else {
+                let text = content[member.value.clone()].to_string();
+                let removed = delete(content, &layout, key)?;
+                insert(&removed, &json_layout(&removed), to, &text)
+            }
+        }
+        KeyEdit::Delete { key } => delete(content, &layout, key),
+    }
+}
+
+fn parent_of<'a>(layout: &'a JsonLayout, key: &str) -> Option<(&'a str, &'a JsonObject)> {
+    layout
+        .objects
+        .iter()
+        .find(|(_, object)| object.members.iter().any(|m| m == key))
+        .map(|(path, object)| (path.as_str(), object))
+}
+
+fn delete(content: &str, layout: &JsonLayout, key: &str) -> Result<String, String> {
+    let member = layout.members.get(key).ok_or_else(|| format!("'{}' not found", key))?;
+    let (parent_path, parent) = parent_of(layout, key).ok_or_else(|| format!("'{}' not found", key))?;
+    // An emptied object would leave nothing behind once flattened, so it goes too
+    if parent.members.len() == 1 && !parent_path.is_empty() {
+        return delete(content, layout, parent_path);
+    }
+
+    let index = parent.members.iter().position(|m| m == key).unwrap_or_default();
+    let range = if let Some(next) = parent.members.get(index + 1) {
+        member.key.start..layout.members[next].key.start
+    } else if index > 0 {
+        layout.members[&parent.members[index - 1]].value.end..member.value.end
+    } else {
+        parent.open + 1..parent.close
+    };
+    Ok(replace_range(content, range, ""))
+}
+
+// Adds `key` below the deepest object that already exists on its path
+fn insert(content: &str, layout: &JsonLayout, key: &str, text: &str) -> Result<String, String> {
+    let segments: Vec<&str> = key.split('.').collect();
+    for depth in (0..segments.len()).rev() {
+        let prefix = segments[..depth].join(".");
+        if let Some(object) = layout.objects.get(&prefix) {
+            return Ok(insert_into(content, layout, object, prefix.is_empty(), &segments[depth..], text));
+        }
+        if layout.members.contains_key(&prefix) {
+            return Err(format!("'{}' is not an object", prefix));
+        }
+    }
+    Err("the document is not an object".to_string())
+}
+
+fn insert_into(content: &str, layout: &JsonLayout, object: &JsonObject, root: bool, segments: &[&str], text: &str) -> String {
+    let newline = newline_of(content);
+    let multiline = content[object.open..object.close].contains('\n') || (root && object.members.is_empty());
+    let object_indent = indent_at(content, object.open);
+    let member_indent = match object.members.first() {
+        Some(first) => indent_at(content, layout.members[first].key.start).to_string(),
+        None => format!("{}  ", object_indent),
+    };
+    let unit = member_indent
+        .strip_prefix(object_indent)
+        .filter(|unit| !unit.is_empty())
+        .unwrap_or("  ");
+    let member = render_member(segments, text, &member_indent, unit, multiline, newline);
+
+    match object.members.last() {
+        Some(last) => {
+            let end = layout.members[last].value.end;
+            let addition = if multiline {
+                format!(",{}{}{}", newline, member_indent, member)
+            } else {
+                format!(", {}", member)
+            };
+            replace_range(content, end..end, &addition)
+        }
+        None => {
+            let body = if multiline {
+                format!("{}{}{}{}{}", newline, member_indent, member, newline, object_indent)
+            } else {
+                member
+            };
+            replace_range(content, object.open + 1..object.close, &body)
+        }
+    }
+}
+
+// `"a": {"b": text}` for the segments ["a", "b"], laid out like the surrounding object
+fn render_member(segments: &[&str], text: &str, indent: &str, unit: &str, multiline: bool, newline: &str) -> String {
+    let key = quoted(segments[0]);
+    if segments.len() == 1 {
+        return format!("{}: {}", key, text);
+    }
+    let inner_indent = format!("{}{}", indent, unit);
+    let inner = render_member(&segments[1..], text, &inner_indent, unit, multiline, newline);
+    if multiline {
+        format!("{}: {{{}{}{}{}{}}}", key, newline, inner_indent, inner, newline, indent)
+    } else {
+        format!("{}: {{{}}}", key, inner)
+    }
+}
+
+fn quoted(name: &str) -> String {
+    serde_json::Value::String(name.to_string()).to_string()
+}
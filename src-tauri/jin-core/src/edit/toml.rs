@@ -0,0 +1,187 @@
+use toml_edit::{DocumentMut, Entry, InlineTable, Item, Key, Table, TableLike, Value};
+
+use super::{split_key, KeyEdit};
+
+pub(super) fn edit(content: &str, edit: &KeyEdit) -> Result<String, String> {
+    let mut document: DocumentMut = content.parse().map_err(|e: toml_edit::TomlError| e.to_string())?;
+    let root = document.as_item_mut();
+    match edit {
+        KeyEdit::Set { key, value } => insert(root, &segments(key), Item::Value(to_value(value)?))?,
+        KeyEdit::Rename { key, to } => {
+            let (parent, name) = split_key(key);
+            let (new_parent, new_name) = split_key(to);
+            if parent == new_parent {
+                let table = find(root, &segments(parent)).ok_or_else(|| format!("'{}' not found", key))?;
+                rename_in(table, name, new_name);
+            } else {
+                let item = remove(root, &segments(key))?;
+                insert(root, &segments(to), item)?;
+            }
+        }
+        KeyEdit::Delete { key } => {
+            remove(root, &segments(key))?;
+        }
+    }
+    Ok(document.to_string())
+}
+
+fn segments(key: &str) -> Vec<&str> {
+    if key.is_empty() {
+        Vec::new()
+    } else {
+        key.split('.').collect()
+    }
+}
+
+fn find<'a>(item: &'a mut Item, segments: &[&str]) -> Option<&'a mut dyn TableLike> {
+    match segments.split_first() {
+        None => item.as_table_like_mut(),
+        Some((first, rest)) => find(item.as_table_like_mut()?.get_mut(first)?, rest),
+    }
+}
+
+// Sets a value in place, keeping its comments and spacing, or adds it with any missing tables
+fn insert(item: &mut Item, segments: &[&str], new: Item) -> Result<(), String> {
+    let inline = item.is_inline_table();
+    let table = item
+        .as_table_like_mut()
+        .ok_or_else(|| "not a table".to_string())?;
+    let Some((first, rest)) = segments.split_first() else {
+        return Err("empty key".to_string());
+    };
+
+    if rest.is_empty() {
+        match (table.get_mut(first), new) {
+            (Some(Item::Value(old)), Item::Value(value)) => {
+                let mut value = keep_literal(old, value);
+                *value.decor_mut() = old.decor().clone();
+                *old = value;
+            }
+            (Some(_), _) => return Err(format!("'{}' is a table", first)),
+            (None, new) => {
+                table.insert(first, new);
+            }
+        }
+        return Ok(());
+    }
+
+    if !table.contains_key(first) {
+        let child = if inline {
+            Item::Value(Value::InlineTable(InlineTable::new()))
+        } else {
+            // Only shows a header once it holds values of its own
+            let mut child = Table::new();
+            child.set_implicit(true);
+            Item::Table(child)
+        };
+        table.insert(first, child);
+    }
+    let child = table.get_mut(first).ok_or_else(|| format!("'{}' not found", first))?;
+    insert(child, rest, new)
+}
+
+// Removes the item and any tables it leaves empty
+fn remove(item: &mut Item, segments: &[&str]) -> Result<Item, String> {
+    let table = item
+        .as_table_like_mut()
+        .ok_or_else(|| "not a table".to_string())?;
+    let Some((first, rest)) = segments.split_first() else {
+        return Err("empty key".to_string());
+    };
+    if rest.is_empty() {
+        keep_comments(table, first);
+        return table.remove(first).ok_or_else(|| format!("'{}' not found", first));
+    }
+
+    let child = table.get_mut(first).ok_or_else(|| format!("'{}' not found", first))?;
+    let removed = remove(child, rest)?;
+    if child.as_table_like().is_some_and(|t| t.is_empty()) {
+        table.remove(first);
+    }
+    Ok(removed)
+}
+
+// Renames a key where it stands: later keys are taken out and put back in the same order
+fn rename_in(table: &mut dyn TableLike, old: &str, new: &str) {
+    let keys: Vec<Key> = table
+        .iter()
+        .filter_map(|(name, _)| table.key(name).cloned())
+        .collect();
+    let Some(index) = keys.iter().position(|key| key.get() == old) else {
+        return;
+    };
+
+    let mut tail = Vec::new();
+    for key in &keys[index..] {
+        if let Some(item) = table.remove(key.get()) {
+            tail.push((key.clone(), item));
+        }
+    }
+    for (key, item) in tail {
+        let key = if key.get() == old {
+            Key::new(new)
+                .with_leaf_decor(key.leaf_decor().clone())
+                .with_dotted_decor(key.dotted_decor().clone())
+        } else {
+            key
+        };
+        if let Entry::Vacant(entry) = table.entry_format(&key) {
+            entry.insert(item);
+        }
+    }
+}
+
+// Comments above a removed key stay, above the key that follows it
+fn keep_comments(table: &mut dyn TableLike, name: &str) {
+    let prefix = table
+        .key(name)
+        .and_then(|key| key.leaf_decor().prefix())
+        .and_then(|prefix| prefix.as_str())
+        .filter(|prefix| prefix.contains('#'))
+        .map(|prefix| prefix.to_string());
+    let Some(prefix) = prefix else {
+        return;
+    };
+    let names: Vec<String> = table.iter().map(|(name, _)| name.to_string()).collect();
+    let Some(next) = names.iter().skip_while(|n| *n != name).nth(1) else {
+        return;
+    };
+    // A table's comments sit before its header, not on its key
+    if let Some(Item::Table(next_table)) = table.get_mut(next) {
+        let own = next_table.decor().prefix().and_then(|p| p.as_str()).unwrap_or_default().to_string();
+        next_table.decor_mut().set_prefix(format!("{}{}", prefix, own));
+    } else if let Some(mut key) = table.key_mut(next) {
+        let own = key.leaf_decor().prefix().and_then(|p| p.as_str()).unwrap_or_default().to_string();
+        key.leaf_decor_mut().set_prefix(format!("{}{}", prefix, own));
+    }
+}
+
+// Literal strings ('...') stay literal when the new value can be one
+fn keep_literal(old: &Value, new: Value) -> Value {
+    let literal = match old {
+        Value::String(formatted) => formatted
+            .as_repr()
+            .and_then(|repr| repr.as_raw().as_str())
+            .is_some_and(|raw| raw.starts_with('\'') && !raw.starts_with("'''")),
+        _ => false,
+    };
+    match new.as_str() {
+        Some(s) if literal && !s.contains(['\'', '\n', '\r']) => {
+            format!("'{}'", s).parse().unwrap_or(new)
+        }
+        _ => new,
+    }
+}
+
+fn to_value(value: &serde_json::Value) -> Result<Value, String> {
+    match value {
+        serde_json::Value::String(s) => Ok(Value::from(s.as_str())),
+        serde_json::Value::Bool(b) => Ok(Value::from(*b)),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => Ok(Value::from(i)),
+            None => Ok(Value::from(n.as_f64().unwrap_or_default())),
+        },
+        serde_json::Value::Null => Err("TOML has no null value".to_string()),
+        _ => Err("only strings, numbers and booleans can be set".to_string()),
+    }
+}
@@ -0,0 +1,587 @@
+use std::collections::HashMap;
+use std::ops::Range;
+use yaml_rust2::parser::{Event, Parser};
+use yaml_rust2::scanner::TScalarStyle;
+
+use super::{indent_at, line_start, newline_of, next_line_start, replace_range, split_key, KeyEdit};
+
+pub(super) fn edit(content: &str, edit: &KeyEdit) -> Result<String, String> {
+    let layout = layout(content);
+    match edit {
+        KeyEdit::Set { key, value } => match layout.members.get(key) {
+            Some(member) => {
+                let text = render_value(value, &content[member.value.clone()], member.flow);
+                let text = if member.after_colon { format!(" {}", text) } else { text };
+                Ok(replace_range(content, member.value.clone(), &text))
+            }
+            None => {
+                let body = Body {
+                    first: format!(": {}", render_value(value, "", false)),
+                    rest: Vec::new(),
+                };
+                insert(content, &layout, key, &body)
+            }
+        },
+        KeyEdit::Rename { key, to } => {
+            let member = layout.members.get(key).ok_or_else(|| format!("'{}' not found", key))?;
+            let (new_parent, name) = split_key(to);
+            if new_parent == member.parent {
+                // Same mapping: only the key changes
+                Ok(replace_range(content, member.key.clone(), &render_key(name)))
+            } else {
+                let body = body_of(content, member);
+                let removed = delete(content, &layout, key)?;
+                insert(&removed, &self::layout(&removed), to, &body)
+            }
+        }
+        KeyEdit::Delete { key } => delete(content, &layout, key),
+    }
+}
+
+#[derive(Default)]
+struct Layout {
+    members: HashMap<String, Member>,
+    mappings: HashMap<String, Mapping>,
+}
+
+struct Member {
+    parent: String,
+    key: Range<usize>,
+    /// Text a set replaces: the scalar as written, or everything after the colon for other values
+    value: Range<usize>,
+    /// Whether `value` starts right after the colon, so a new value needs a space before it
+    after_colon: bool,
+    /// End of the member's text, without trailing comments or blank lines
+    end: usize,
+    /// Whether the member sits in a flow mapping (`{a: 1}`)
+    flow: bool,
+    kind: Kind,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Plain,
+    Quoted,
+    /// `|` and `>` scalars
+    Block,
+    Empty,
+    /// Collections and aliases
+    Other,
+}
+
+struct Mapping {
+    flow: bool,
+    /// Offsets of `{` and `}` for flow mappings
+    open: usize,
+    close: usize,
+    members: Vec<String>,
+}
+
+// A key's text after its name: `: value` on the key's line, then any further lines, unindented
+struct Body {
+    first: String,
+    rest: Vec<String>,
+}
+
+// Walks the parser events to find where each flattened key and its value are written. Paths below
+// sequences aren't tracked, as the parser keeps sequences as values instead of flattening them.
+fn layout(content: &str) -> Layout {
+    struct Frame {
+        mapping: bool,
+        flow: bool,
+        // Path of the keys inside, when they are flattened
+        path: Option<String>,
+        // Key waiting for its value; None inside when the key isn't tracked
+        key: Option<Option<String>>,
+        // Block mappings finish a member once the next one (or the mapping's end) is found
+        last: Option<String>,
+        // Member this collection is the value of
+        owner: Option<String>,
+    }
+
+    // What a node is to the mapping around it
+    enum Role {
+        Root,
+        Key(Option<String>),
+        Value(Option<String>),
+    }
+
+    fn untracked(mapping: bool) -> Frame {
+        Frame {
+            mapping,
+            flow: false,
+            path: None,
+            key: None,
+            last: None,
+            owner: None,
+        }
+    }
+
+    let offsets: Vec<usize> = content
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain([content.len()])
+        .collect();
+    let mut layout = Layout::default();
+    let mut stack: Vec<Frame> = Vec::new();
+    let mut parser = Parser::new_from_str(content);
+
+    while let Ok((event, marker)) = parser.next_token() {
+        let pos = offsets[marker.index().min(offsets.len() - 1)];
+        let (style, start, collection) = match event {
+            Event::StreamEnd | Event::DocumentEnd => break,
+            Event::Scalar(ref value, style, ..) => (Some((value.clone(), style)), None, None),
+            Event::Alias(_) => (None, None, None),
+            Event::MappingStart(..) => (None, Some(true), None),
+            Event::SequenceStart(..) => (None, Some(false), None),
+            Event::MappingEnd | Event::SequenceEnd => (None, None, Some(())),
+            _ => continue,
+        };
+
+        if collection.is_some() {
+            let Some(frame) = stack.pop() else { continue };
+            if let (true, Some(path)) = (frame.mapping, &frame.path) {
+                if let Some(last) = &frame.last {
+                    finish(content, &mut layout, last, pos);
+                }
+                if let Some(mapping) = layout.mappings.get_mut(path) {
+                    mapping.close = pos;
+                }
+            }
+            if let (true, Some(owner)) = (frame.flow, &frame.owner) {
+                if let Some(member) = layout.members.get_mut(owner) {
+                    member.value.end = pos + 1;
+                    member.end = pos + 1;
+                }
+            }
+            continue;
+        }
+
+        // Every other event is a node: a key, a value, or something untracked
+        let (parent_flow, parent_path, role) = match stack.last_mut() {
+            Some(frame) if frame.mapping => match frame.key.take() {
+                None => {
+                    let key = match (&frame.path, &style) {
+                        (Some(prefix), Some((name, _))) => Some(join(prefix, name)),
+                        _ => None,
+                    };
+                    frame.key = Some(key.clone());
+                    (frame.flow, frame.path.clone(), Role::Key(key))
+                }
+                Some(key) => (frame.flow, frame.path.clone(), Role::Value(key)),
+            },
+            Some(_) => (false, None, Role::Value(None)),
+            None => (false, None, Role::Root),
+        };
+
+        match role {
+            Role::Key(Some(path)) => {
+                let parent = parent_path.unwrap_or_default();
+                let quoted = matches!(style, Some((_, TScalarStyle::SingleQuoted | TScalarStyle::DoubleQuoted)));
+                let key_end = if quoted { quoted_end(content, pos) } else { plain_key_end(content, pos) };
+                if let Some(frame) = stack.last_mut() {
+                    if !frame.flow {
+                        if let Some(last) = frame.last.replace(path.clone()) {
+                            finish(content, &mut layout, &last, pos);
+                        }
+                    }
+                }
+                if let Some(mapping) = layout.mappings.get_mut(&parent) {
+                    mapping.members.push(path.clone());
+                }
+                layout.members.insert(
+                    path,
+                    Member {
+                        parent,
+                        key: pos..key_end,
+                        value: key_end..key_end,
+                        after_colon: false,
+                        end: key_end,
+                        flow: parent_flow,
+                        kind: Kind::Empty,
+                    },
+                );
+            }
+            Role::Value(Some(path)) => {
+                if let Some(member) = layout.members.get_mut(&path) {
+                    let colon = member.key.end + content[member.key.end..].find(':').unwrap_or(0);
+                    let kind = match (&style, start) {
+                        (Some((value, TScalarStyle::Plain)), _) if value.is_empty() => Kind::Empty,
+                        (Some((_, TScalarStyle::Plain)), _) => Kind::Plain,
+                        (Some((_, TScalarStyle::SingleQuoted | TScalarStyle::DoubleQuoted)), _) => Kind::Quoted,
+                        (Some(_), _) => Kind::Block,
+                        _ => Kind::Other,
+                    };
+                    member.kind = kind;
+                    let flow_value = parent_flow || opens_flow(content, pos);
+                    member.value = match kind {
+                        Kind::Quoted => pos..quoted_end(content, pos),
+                        Kind::Plain if parent_flow => pos..flow_plain_end(content, pos),
+                        Kind::Plain => pos..pos,
+                        Kind::Block => {
+                            let indicator = colon + 1 + (content[colon + 1..].len() - content[colon + 1..].trim_start().len());
+                            indicator..indicator
+                        }
+                        Kind::Other if flow_value => pos..alias_end(content, pos),
+                        Kind::Empty | Kind::Other => {
+                            member.after_colon = true;
+                            colon + 1..colon + 1
+                        }
+                    };
+                    member.end = member.value.end.max(colon + 1);
+                }
+                if start.is_some() {
+                    stack.push(Frame {
+                        mapping: start == Some(true),
+                        flow: opens_flow(content, pos),
+                        path: if start == Some(true) { Some(path.clone()) } else { None },
+                        key: None,
+                        last: None,
+                        owner: Some(path),
+                    });
+                }
+            }
+            Role::Root => {
+                if start == Some(true) {
+                    stack.push(Frame {
+                        mapping: true,
+                        flow: opens_flow(content, pos),
+                        path: Some(String::new()),
+                        key: None,
+                        last: None,
+                        owner: None,
+                    });
+                } else if start.is_some() {
+                    stack.push(untracked(false));
+                }
+            }
+            Role::Key(None) | Role::Value(None) => {
+                if let Some(mapping) = start {
+                    stack.push(untracked(mapping));
+                }
+            }
+        }
+
+        // Tracked mappings are recorded as they open
+        if start == Some(true) {
+            if let Some(Frame { path: Some(path), flow, .. }) = stack.last() {
+                layout.mappings.insert(
+                    path.clone(),
+                    Mapping {
+                        flow: *flow,
+                        open: pos,
+                        close: pos,
+                        members: Vec::new(),
+                    },
+                );
+            }
+        }
+    }
+
+    layout
+}
+
+// Ends a block mapping member before `next`, the start of whatever follows it
+fn finish(content: &str, layout: &mut Layout, path: &str, next: usize) {
+    let Some(member) = layout.members.get_mut(path) else { return };
+    let key_indent = indent_at(content, member.key.start).len();
+    // Step back over blank lines and comments; block scalars keep indented `#` lines as text
+    let mut end = line_start(content, next);
+    while end > member.key.end {
+        let start = line_start(content, end - 1);
+        let line = content[start..end].trim();
+        let indent = content[start..end].len() - content[start..end].trim_start().len();
+        let comment = line.starts_with('#') && (member.kind != Kind::Block || indent <= key_indent);
+        if !line.is_empty() && !comment {
+            break;
+        }
+        end = start;
+    }
+    let end = content[..end].trim_end().len().max(member.end);
+    member.end = end;
+
+    match member.kind {
+        Kind::Plain => {
+            // Plain scalars can't hold ` #`, so one on the last line starts a comment
+            let from = member.value.start.max(line_start(content, end));
+            let last = &content[from..end];
+            let cut = last.find(" #").or_else(|| last.find("\t#")).unwrap_or(last.len());
+            member.value.end = from + last[..cut].trim_end().len();
+        }
+        Kind::Block => member.value.end = end,
+        Kind::Other if member.after_colon => member.value.end = end,
+        _ => {}
+    }
+}
+
+// Collection events point at `{` or `[` for flow collections; block sequences point past
+// their first `-`, which may be followed by a flow collection of its own
+fn opens_flow(content: &str, pos: usize) -> bool {
+    content[pos..].starts_with(['{', '[']) && !content[..pos].trim_end().ends_with('-')
+}
+
+fn join(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", prefix, key)
+    }
+}
+
+// End of a '...' or "..." scalar starting at `start`
+fn quoted_end(content: &str, start: usize) -> usize {
+    let bytes = content.as_bytes();
+    let quote = bytes[start];
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' if quote == b'"' => i += 2,
+            b'\'' if quote == b'\'' && bytes.get(i + 1) == Some(&b'\'') => i += 2,
+            b if b == quote => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+// A plain key runs up to the `:` that separates it from its value
+fn plain_key_end(content: &str, start: usize) -> usize {
+    let rest = &content[start..];
+    let colon = rest
+        .char_indices()
+        .find(|&(i, c)| {
+            c == ':' && rest[i + 1..].chars().next().is_none_or(|n| n.is_whitespace() || ",[]{}".contains(n))
+        })
+        .map(|(i, _)| i)
+        .unwrap_or(rest.len());
+    start + rest[..colon].trim_end().len()
+}
+
+fn flow_plain_end(content: &str, start: usize) -> usize {
+    let rest = &content[start..];
+    let mut end = rest.find([',', ']', '}', '\n', '\r']).unwrap_or(rest.len());
+    if let Some(comment) = rest[..end].find(" #") {
+        end = comment;
+    }
+    start + rest[..end].trim_end().len()
+}
+
+// End of an alias (`*name`) or of a flow collection starting at `start`
+fn alias_end(content: &str, start: usize) -> usize {
+    let rest = &content[start..];
+    if !rest.starts_with(['{', '[']) {
+        return start + rest.find(|c: char| c.is_whitespace() || ",]}".contains(c)).unwrap_or(rest.len());
+    }
+    // Flow collections are closed by their end event; until then, the opening bracket
+    start + 1
+}
+
+fn delete(content: &str, layout: &Layout, key: &str) -> Result<String, String> {
+    let member = layout.members.get(key).ok_or_else(|| format!("'{}' not found", key))?;
+    let parent = layout
+        .mappings
+        .get(&member.parent)
+        .ok_or_else(|| format!("'{}' not found", key))?;
+    // An emptied mapping would leave nothing behind once flattened, so it goes too
+    if parent.members.len() == 1 && !member.parent.is_empty() {
+        return delete(content, layout, &member.parent);
+    }
+
+    if !parent.flow {
+        let range = line_start(content, member.key.start)..next_line_start(content, member.end);
+        return Ok(replace_range(content, range, ""));
+    }
+    let index = parent.members.iter().position(|m| m == key).unwrap_or_default();
+    let range = if let Some(next) = parent.members.get(index + 1) {
+        member.key.start..layout.members[next].key.start
+    } else if index > 0 {
+        layout.members[&parent.members[index - 1]].end..member.end
+    } else {
+        parent.open + 1..parent.close
+    };
+    Ok(replace_range(content, range, ""))
+}
+
+// The member's text after its key, with continuation lines unindented
+fn body_of(content: &str, member: &Member) -> Body {
+    let key_line_end = content[member.key.end..]
+        .find(['\r', '\n'])
+        .map(|i| member.key.end + i)
+        .unwrap_or(content.len());
+    if member.flow || member.end <= key_line_end {
+        return Body {
+            first: content[member.key.end..member.end].to_string(),
+            rest: Vec::new(),
+        };
+    }
+    let indent = indent_at(content, member.key.start).len();
+    let rest = content[next_line_start(content, key_line_end)..member.end]
+        .lines()
+        .map(|line| {
+            let blanks = line.len() - line.trim_start_matches(' ').len();
+            line[blanks.min(indent)..].to_string()
+        })
+        .collect();
+    Body {
+        first: content[member.key.end..key_line_end].to_string(),
+        rest,
+    }
+}
+
+// Adds `key` below the deepest mapping that already exists on its path
+fn insert(content: &str, layout: &Layout, key: &str, body: &Body) -> Result<String, String> {
+    let segments: Vec<&str> = key.split('.').collect();
+    for depth in (0..segments.len()).rev() {
+        let prefix = segments[..depth].join(".");
+        if let Some(mapping) = layout.mappings.get(&prefix) {
+            return insert_into(content, layout, mapping, &segments[depth..], body);
+        }
+        if layout.members.contains_key(&prefix) {
+            return Err(format!("'{}' is not a mapping", prefix));
+        }
+    }
+    // A document without a mapping can take one if it holds nothing else
+    let bare = content
+        .lines()
+        .all(|line| line.trim().is_empty() || line.trim_start().starts_with('#') || line.trim() == "---");
+    if !bare {
+        return Err("the document is not a mapping".to_string());
+    }
+    let newline = newline_of(content);
+    let mut updated = content.to_string();
+    if !updated.is_empty() && !updated.ends_with('\n') {
+        updated.push_str(newline);
+    }
+    updated.push_str(&render_block(&segments, body, "", &unit(content, layout), newline));
+    Ok(updated)
+}
+
+fn insert_into(content: &str, layout: &Layout, mapping: &Mapping, segments: &[&str], body: &Body) -> Result<String, String> {
+    if mapping.flow {
+        if !body.rest.is_empty() {
+            return Err(format!("'{}' spans several lines and can't go into a flow mapping", segments.join(".")));
+        }
+        let member = render_flow(segments, &body.first);
+        return Ok(match mapping.members.last() {
+            Some(last) => {
+                let end = layout.members[last].end;
+                replace_range(content, end..end, &format!(", {}", member))
+            }
+            None => replace_range(content, mapping.open + 1..mapping.close, &member),
+        });
+    }
+
+    let newline = newline_of(content);
+    let last = mapping
+        .members
+        .last()
+        .and_then(|last| layout.members.get(last))
+        .ok_or_else(|| "mapping has no keys".to_string())?;
+    let indent = indent_at(content, last.key.start);
+    let at = next_line_start(content, last.end);
+    let mut text = render_block(segments, body, indent, &unit(content, layout), newline);
+    if at == content.len() && !content.ends_with('\n') {
+        text.insert_str(0, newline);
+    }
+    Ok(replace_range(content, at..at, &text))
+}
+
+// Lines for `a:` / `  b: value`, ending with a newline
+fn render_block(segments: &[&str], body: &Body, indent: &str, unit: &str, newline: &str) -> String {
+    let mut text = String::new();
+    let mut indent = indent.to_string();
+    for (i, segment) in segments.iter().enumerate() {
+        text.push_str(&indent);
+        text.push_str(&render_key(segment));
+        if i + 1 < segments.len() {
+            text.push(':');
+            text.push_str(newline);
+            indent.push_str(unit);
+        }
+    }
+    text.push_str(&body.first);
+    text.push_str(newline);
+    for line in &body.rest {
+        if !line.is_empty() {
+            text.push_str(&indent);
+            text.push_str(line);
+        }
+        text.push_str(newline);
+    }
+    text
+}
+
+// `a: {b: value}` for the segments ["a", "b"]
+fn render_flow(segments: &[&str], first: &str) -> String {
+    let key = render_key(segments[0]);
+    if segments.len() == 1 {
+        return format!("{}{}", key, first);
+    }
+    format!("{}: {{{}}}", key, render_flow(&segments[1..], first))
+}
+
+// The document's indentation step, from its first nested block mapping
+fn unit(content: &str, layout: &Layout) -> String {
+    let mut nested: Vec<(&Member, &Member)> = layout
+        .mappings
+        .iter()
+        .filter(|(path, mapping)| !path.is_empty() && !mapping.flow)
+        .filter_map(|(path, mapping)| {
+            let first = layout.members.get(mapping.members.first()?)?;
+            Some((layout.members.get(path)?, first))
+        })
+        .collect();
+    nested.sort_by_key(|(_, child)| child.key.start);
+    nested
+        .first()
+        .and_then(|(parent, child)| {
+            let parent = indent_at(content, parent.key.start);
+            indent_at(content, child.key.start)
+                .strip_prefix(parent)
+                .filter(|unit| !unit.is_empty())
+                .map(|unit| unit.to_string())
+        })
+        .unwrap_or_else(|| "  ".to_string())
+}
+
+fn render_key(name: &str) -> String {
+    if plain_safe(name, true) {
+        name.to_string()
+    } else {
+        serde_json::Value::String(name.to_string()).to_string()
+    }
+}
+
+// Writes a value in the quoting `like` (the old value as written) uses, when it can hold it
+fn render_value(value: &serde_json::Value, like: &str, flow: bool) -> String {
+    match value {
+        serde_json::Value::String(s) => {
+            let printable = !s.chars().any(char::is_control);
+            if like.starts_with('\'') && printable {
+                format!("'{}'", s.replace('\'', "''"))
+            } else if !like.starts_with('"') && plain_safe(s, flow) {
+                s.clone()
+            } else {
+                value.to_string()
+            }
+        }
+        serde_json::Value::Null => "null".to_string(),
+        other => other.to_string(),
+    }
+}
+
+// Whether `s` reads back as the same string without quotes
+fn plain_safe(s: &str, flow: bool) -> bool {
+    let indicators = [
+        '-', '?', ':', ',', '[', ']', '{', '}', '#', '&', '*', '!', '|', '>', '\'', '"', '%', '@', '`',
+    ];
+    !s.is_empty()
+        && s.trim() == s
+        && !s.starts_with(indicators)
+        && !s.ends_with(':')
+        && !s.contains(": ")
+        && !s.contains(" #")
+        && !s.chars().any(char::is_control)
+        && !(flow && s.contains([',', '[', ']', '{', '}']))
+        && matches!(serde_yaml::from_str::<serde_yaml::Value>(s), Ok(serde_yaml::Value::String(ref read)) if read == s)
+}
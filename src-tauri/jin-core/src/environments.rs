@@ -0,0 +1,189 @@
+//! Environment groups: which files describe the same deployment, and which template lists the
+//! keys every environment is expected to define.
+//!
+//! Without configuration, `.env.<name>` and `.env.<name>.local` form the `<name>` environment, and
+//! structured files such as `config/production.yaml` or `appsettings.Production.json` join it when
+//! their name ends in `<name>`. `.env` and `.env.local` are shared by every environment.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use crate::scanner::build_glob_set;
+
+/// Template files recognized when `[environments] templates` is not set.
+pub const DEFAULT_TEMPLATES: &[&str] = &[".env.example", ".env.sample", ".env.template", ".env.dist"];
+
+/// `[environments]` in jin.toml. Unknown fields are errors, so a misspelled setting isn't ignored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EnvironmentsConfig {
+    /// Globs of template files; each env file is checked against the nearest one at or above it
+    pub templates: Vec<String>,
+    /// Group files into environments by name when no explicit group matches them
+    pub infer: bool,
+    /// `[environments.groups]`: environment name -> globs of the files belonging to it
+    pub groups: BTreeMap<String, Vec<String>>,
+}
+
+impl Default for EnvironmentsConfig {
+    fn default() -> Self {
+        Self {
+            templates: DEFAULT_TEMPLATES.iter().map(|t| t.to_string()).collect(),
+            infer: true,
+            groups: BTreeMap::new(),
+        }
+    }
+}
+
+impl EnvironmentsConfig {
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        build_glob_set(&self.templates).map_err(|e| format!("environments.templates: {}", e))?;
+        for (name, patterns) in &self.groups {
+            build_glob_set(patterns).map_err(|e| format!("environments.groups.{}: {}", name, e))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Environment {
+    pub name: String,
+    pub files: Vec<String>,
+}
+
+/// Scanned files sorted into templates and environments.
+#[derive(Debug, Clone, Default)]
+pub struct EnvironmentMap {
+    pub environments: Vec<Environment>,
+    pub templates: Vec<String>,
+    membership: HashMap<String, String>,
+}
+
+impl EnvironmentMap {
+    /// Classifies `files` (path and whether it is an env file).
+    pub fn build(files: &[(String, bool)], config: &EnvironmentsConfig) -> Self {
+        // Invalid globs are rejected when jin.toml is loaded, so skipping them here is safe
+        let templates = build_glob_set(&config.templates).ok();
+        let groups: Vec<_> = config
+            .groups
+            .iter()
+            .filter_map(|(name, patterns)| build_glob_set(patterns).ok().map(|set| (name, set)))
+            .collect();
+
+        let mut map = EnvironmentMap::default();
+        let mut unassigned = Vec::new();
+
+        for (file, is_env) in files {
+            let path = Path::new(file);
+            if templates.as_ref().map(|t| t.is_match(path)).unwrap_or(false) {
+                map.templates.push(file.clone());
+            } else if let Some((name, _)) = groups.iter().find(|(_, set)| set.is_match(path)) {
+                map.membership.insert(file.clone(), name.to_string());
+            } else if config.infer {
+                unassigned.push((file, *is_env));
+            }
+        }
+
+        // Env files name the environments; structured files can only join one that exists
+        for (file, is_env) in &unassigned {
+            if let Some(name) = is_env.then(|| env_file_mode(file)).flatten() {
+                map.membership.insert(file.to_string(), name);
+            }
+        }
+        let mut names: Vec<String> = map.membership.values().cloned().collect();
+        names.extend(config.groups.keys().cloned());
+        for (file, is_env) in &unassigned {
+            if *is_env {
+                continue;
+            }
+            let suffix = Path::new(file)
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.rsplit('.').next())
+                .unwrap_or_default();
+            if let Some(name) = names.iter().find(|name| name.eq_ignore_ascii_case(suffix)) {
+                map.membership.insert(file.to_string(), name.clone());
+            }
+        }
+
+        let mut grouped: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (file, name) in &map.membership {
+            grouped.entry(name.clone()).or_default().push(file.clone());
+        }
+        map.environments = grouped
+            .into_iter()
+            .map(|(name, mut files)| {
+                files.sort();
+                Environment { name, files }
+            })
+            .collect();
+        map.templates.sort();
+
+        map
+    }
+
+    pub fn environment_of(&self, file: &str) -> Option<&str> {
+        self.membership.get(file).map(|name| name.as_str())
+    }
+
+    pub fn is_template(&self, file: &str) -> bool {
+        self.templates.iter().any(|t| t == file)
+    }
+
+    /// The template in the same directory as `file`, or else in the nearest parent directory.
+    pub fn template_for(&self, file: &str) -> Option<&str> {
+        let mut dir = Path::new(file).parent();
+        while let Some(current) = dir {
+            let found = self
+                .templates
+                .iter()
+                .find(|t| Path::new(t).parent().unwrap_or(Path::new("")) == current);
+            if let Some(template) = found {
+                return Some(template);
+            }
+            dir = current.parent();
+        }
+        None
+    }
+}
+
+/// `production` for `.env.production` and `.env.production.local`; `None` for `.env` and `.env.local`.
+fn env_file_mode(file: &str) -> Option<String> {
+    let name = Path::new(file).file_name()?.to_str()?;
+    let rest = name.strip_prefix(".env.")?;
+    let mode = rest.strip_suffix(".local").unwrap_or(rest);
+    (!mode.is_empty() && mode != "local").then(|| mode.to_string())
+}
+
+/// Env files loaded before `file` in the same directory, following the dotenv-flow order
+/// `.env` → `.env.<mode>` → `.env.local` → `.env.<mode>.local`. Nearest first.
+pub fn load_order(file: &str) -> Vec<String> {
+    let path = Path::new(file);
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return Vec::new();
+    };
+    let dir = path.parent().unwrap_or(Path::new(""));
+
+    let chain: Vec<String> = match name {
+        ".env" => vec![],
+        ".env.local" => vec![".env".to_string()],
+        _ => match name.strip_prefix(".env.") {
+            Some(rest) => match rest.strip_suffix(".local") {
+                Some(mode) => vec![
+                    ".env".to_string(),
+                    format!(".env.{}", mode),
+                    ".env.local".to_string(),
+                ],
+                None => vec![".env".to_string()],
+            },
+            None => vec![],
+        },
+    };
+
+    chain
+        .into_iter()
+        .rev()
+        .map(|name| dir.join(name).to_string_lossy().to_string())
+        .collect()
+}
@@ -52,7 +52,7 @@ fn generate_env_format(unique_keys: &[String], key_entry_map: &HashMap<String, &
             lines.push(format!("# Type: {}", type_hint));
 
             // Add secret warning if applicable
-            if entry.is_secret_guess.unwrap_or(false) {
+            if entry.is_secret() {
                 lines.push("# ⚠️ Sensitive value - keep secure".to_string());
             }
 
@@ -82,7 +82,7 @@ fn generate_json_format(unique_keys: &[String], key_entry_map: &HashMap<String,
             };
             metadata.insert("type".to_string(), serde_json::Value::String(type_str.to_string()));
 
-            if entry.is_secret_guess.unwrap_or(false) {
+            if entry.is_secret() {
                 metadata.insert("sensitive".to_string(), serde_json::Value::Bool(true));
             }
 
@@ -124,7 +124,7 @@ fn generate_yaml_format(unique_keys: &[String], key_entry_map: &HashMap<String,
                 serde_yaml::Value::String(type_str.to_string())
             );
 
-            if entry.is_secret_guess.unwrap_or(false) {
+            if entry.is_secret() {
                 metadata.insert(
                     serde_yaml::Value::String("sensitive".to_string()),
                     serde_yaml::Value::Bool(true)
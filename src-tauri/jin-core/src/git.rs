@@ -0,0 +1,143 @@
+//! Whether scanned files are committed, or would be by `git add .`, read through the `git` command
+//! line. This is best effort: outside a work tree, or without git on the PATH, nothing is reported.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::analyzer::env_value_type;
+use crate::secrets::is_placeholder;
+use crate::types::{ExposedSecret, ExposureKind, InferredType, NormalizedEntry, SecretExposure};
+
+/// Index and ignore status of the scanned files.
+#[derive(Debug, Clone, Default)]
+pub struct GitStatus {
+    tracked: HashSet<PathBuf>,
+    ignored: HashSet<PathBuf>,
+}
+
+impl GitStatus {
+    /// Looks up `files`, relative to `root`; `None` when `root` is not inside a git work tree.
+    pub fn load(root: &Path, files: &[&str]) -> Option<Self> {
+        let inside = git(root, &["rev-parse", "--is-inside-work-tree"], None)?;
+        if inside.trim() != "true" {
+            return None;
+        }
+
+        // Relative to `root`, since git runs there
+        let tracked: HashSet<PathBuf> = git(root, &["ls-files", "-z"], None)?
+            .split('\0')
+            .filter(|p| !p.is_empty())
+            .map(PathBuf::from)
+            .collect();
+
+        let untracked: Vec<&str> = files
+            .iter()
+            .copied()
+            .filter(|f| !tracked.contains(Path::new(f)))
+            .collect();
+        let ignored = if untracked.is_empty() {
+            HashSet::new()
+        } else {
+            let stdin = untracked.join("\0") + "\0";
+            git(root, &["check-ignore", "-z", "--stdin"], Some(&stdin))?
+                .split('\0')
+                .filter(|p| !p.is_empty())
+                .map(PathBuf::from)
+                .collect()
+        };
+
+        Some(Self { tracked, ignored })
+    }
+
+    pub fn is_tracked(&self, file: &str) -> bool {
+        self.tracked.contains(Path::new(file))
+    }
+
+    pub fn is_ignored(&self, file: &str) -> bool {
+        self.ignored.contains(Path::new(file))
+    }
+}
+
+/// Files holding real secret values that are tracked, or untracked but not gitignored.
+/// Placeholders such as `changeme` or `${DB_PASSWORD}` don't count, so templates can be committed,
+/// and neither do numbers and booleans under a secret-sounding key (`TOKEN_TTL=3600`).
+pub fn find_exposed_secrets(entries: &[NormalizedEntry], status: &GitStatus) -> Vec<SecretExposure> {
+    let mut by_file: BTreeMap<&str, Vec<ExposedSecret>> = BTreeMap::new();
+    for entry in entries {
+        if !entry.is_secret() || !has_real_value(entry) {
+            continue;
+        }
+        by_file
+            .entry(entry.source_file.as_str())
+            .or_default()
+            .push(ExposedSecret {
+                key: entry.key.clone(),
+                rule: entry.secret_rule.clone(),
+                span: entry.span,
+            });
+    }
+
+    by_file
+        .into_iter()
+        .filter_map(|(file, secrets)| {
+            let kind = if status.is_tracked(file) {
+                ExposureKind::Tracked
+            } else if !status.is_ignored(file) {
+                ExposureKind::NotIgnored
+            } else {
+                return None;
+            };
+            Some(SecretExposure {
+                file: file.to_string(),
+                kind,
+                secrets,
+            })
+        })
+        .collect()
+}
+
+// Structured numbers and booleans aren't strings, so env values that look like them are skipped too
+fn has_real_value(entry: &NormalizedEntry) -> bool {
+    entry
+        .value
+        .as_ref()
+        .and_then(|v| v.as_str())
+        .map(|s| !is_placeholder(s) && env_value_type(entry) == InferredType::String)
+        .unwrap_or(false)
+}
+
+// Runs git in `dir`; `None` if it can't be started or exits with an error. `check-ignore` exits
+// with 1 when nothing is ignored, which is not an error.
+fn git(dir: &Path, args: &[&str], stdin: Option<&str>) -> Option<String> {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let mut child = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .stdin(if stdin.is_some() { Stdio::piped() } else { Stdio::null() })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+
+    // git answers while it reads, so writing everything before reading would block once its
+    // output fills the pipe. The input is written from another thread, which closes it when done.
+    let pipe = child.stdin.take();
+    let output = std::thread::scope(|scope| {
+        if let (Some(input), Some(mut pipe)) = (stdin, pipe) {
+            scope.spawn(move || pipe.write_all(input.as_bytes()));
+        }
+        child.wait_with_output()
+    })
+    .ok()?;
+
+    match output.status.code() {
+        Some(0) => {}
+        Some(1) if args.first() == Some(&"check-ignore") => {}
+        _ => return None,
+    }
+    String::from_utf8(output.stdout).ok()
+}
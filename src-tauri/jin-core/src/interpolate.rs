@@ -0,0 +1,372 @@
+//! `${VAR}` interpolation for env files.
+//!
+//! Supported forms: `$VAR`, `${VAR}`, `${VAR:-default}` (default when unset or empty) and
+//! `${VAR-default}` (default when unset). `$$` and `\$` produce a literal `$`.
+//!
+//! A reference resolves against its own file first and then against the files loaded before it,
+//! following the dotenv-flow order `.env` → `.env.<mode>` → `.env.local` → `.env.<mode>.local`
+//! within the same directory. `KEY=${KEY}:extra` refers to the previous definition of `KEY`.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::environments::load_order;
+use crate::types::{NormalizedEntry, ReferenceCycle, SourceFormat, Span, UndefinedReference};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Reference {
+        name: String,
+        default: Option<String>,
+        // `:-` also applies the default when the variable is set but empty
+        default_if_empty: bool,
+    },
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Resolution {
+    /// Resolved value per (file, key), for entries that contain references
+    pub values: HashMap<(String, String), String>,
+    /// Entries whose resolved value includes the value of a secret
+    pub from_secrets: HashSet<(String, String)>,
+    pub undefined: Vec<UndefinedReference>,
+    pub cycles: Vec<ReferenceCycle>,
+}
+
+/// Names referenced by `value`, including references inside defaults.
+pub fn references(value: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    collect_references(&parse_template(value), &mut names);
+    names
+}
+
+/// Expands a value that has no references, which only removes `$` escapes.
+pub fn unescape(value: &str) -> String {
+    parse_template(value)
+        .into_iter()
+        .map(|segment| match segment {
+            Segment::Literal(text) => text,
+            Segment::Reference { .. } => String::new(),
+        })
+        .collect()
+}
+
+fn collect_references(segments: &[Segment], names: &mut Vec<String>) {
+    for segment in segments {
+        if let Segment::Reference { name, default, .. } = segment {
+            if !names.contains(name) {
+                names.push(name.clone());
+            }
+            if let Some(default) = default {
+                collect_references(&parse_template(default), names);
+            }
+        }
+    }
+}
+
+fn is_name_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_'
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+fn parse_template(value: &str) -> Vec<Segment> {
+    let chars: Vec<char> = value.chars().collect();
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        if (c == '$' || c == '\\') && next == Some('$') {
+            literal.push('$');
+            i += 2;
+            continue;
+        }
+        if c != '$' {
+            literal.push(c);
+            i += 1;
+            continue;
+        }
+
+        let reference = match next {
+            Some('{') => parse_braced(&chars, i + 2),
+            Some(n) if is_name_start(n) => {
+                let end = (i + 1..chars.len())
+                    .find(|&j| !is_name_char(chars[j]))
+                    .unwrap_or(chars.len());
+                let name: String = chars[i + 1..end].iter().collect();
+                Some((
+                    Segment::Reference {
+                        name,
+                        default: None,
+                        default_if_empty: false,
+                    },
+                    end,
+                ))
+            }
+            _ => None,
+        };
+
+        match reference {
+            Some((segment, end)) => {
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                segments.push(segment);
+                i = end;
+            }
+            // A lone or malformed `$` is kept as text
+            None => {
+                literal.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+    segments
+}
+
+// Parses `NAME}`, `NAME:-default}` or `NAME-default}` starting at `start`; returns the segment and
+// the index after the closing brace
+fn parse_braced(chars: &[char], start: usize) -> Option<(Segment, usize)> {
+    if !chars.get(start).copied().map(is_name_start).unwrap_or(false) {
+        return None;
+    }
+    let name_end = (start..chars.len())
+        .find(|&j| !is_name_char(chars[j]))
+        .unwrap_or(chars.len());
+    let name: String = chars[start..name_end].iter().collect();
+
+    let (default_start, default_if_empty) = match (chars.get(name_end), chars.get(name_end + 1)) {
+        (Some('}'), _) => {
+            return Some((
+                Segment::Reference {
+                    name,
+                    default: None,
+                    default_if_empty: false,
+                },
+                name_end + 1,
+            ));
+        }
+        (Some(':'), Some('-')) => (name_end + 2, true),
+        (Some('-'), _) => (name_end + 1, false),
+        _ => return None,
+    };
+
+    // Defaults may nest `${...}`, so match braces
+    let mut depth = 0;
+    for j in default_start..chars.len() {
+        match chars[j] {
+            '{' => depth += 1,
+            '}' if depth == 0 => {
+                return Some((
+                    Segment::Reference {
+                        name,
+                        default: Some(chars[default_start..j].iter().collect()),
+                        default_if_empty,
+                    },
+                    j + 1,
+                ));
+            }
+            '}' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Resolves every env entry that contains references.
+pub fn resolve(entries: &[NormalizedEntry]) -> Resolution {
+    let mut index: HashMap<(&str, &str), &NormalizedEntry> = HashMap::new();
+    for entry in entries {
+        if matches!(entry.source_format, SourceFormat::Env) {
+            index.insert((entry.source_file.as_str(), entry.key.as_str()), entry);
+        }
+    }
+    let env_files: HashSet<&str> = index.keys().map(|(file, _)| *file).collect();
+    let chains: HashMap<&str, Vec<String>> = env_files
+        .iter()
+        .map(|file| {
+            let earlier = load_order(file)
+                .into_iter()
+                .filter(|f| env_files.contains(f.as_str()))
+                .collect();
+            (*file, earlier)
+        })
+        .collect();
+
+    let mut resolver = Resolver {
+        index,
+        chains,
+        memo: HashMap::new(),
+        stack: Vec::new(),
+        resolution: Resolution::default(),
+        seen_cycles: HashSet::new(),
+    };
+
+    for entry in entries {
+        if matches!(entry.source_format, SourceFormat::Env) && !entry.references.is_empty() {
+            if let Some(value) = resolver.resolve_key(&entry.source_file, &entry.key) {
+                resolver
+                    .resolution
+                    .values
+                    .insert((entry.source_file.clone(), entry.key.clone()), value);
+            }
+        }
+    }
+
+    resolver.resolution
+}
+
+/// Fills in `resolved_value` and `references_secret` on env entries that contain references.
+pub fn apply(entries: &mut [NormalizedEntry]) {
+    let Resolution { mut values, from_secrets, .. } = resolve(entries);
+    for entry in entries.iter_mut() {
+        let id = (entry.source_file.clone(), entry.key.clone());
+        if let Some(value) = values.remove(&id) {
+            entry.resolved_value = Some(serde_json::Value::String(value));
+            entry.references_secret = from_secrets.contains(&id);
+        }
+    }
+}
+
+struct Resolver<'a> {
+    index: HashMap<(&'a str, &'a str), &'a NormalizedEntry>,
+    chains: HashMap<&'a str, Vec<String>>,
+    memo: HashMap<(String, String), Option<String>>,
+    stack: Vec<(String, String)>,
+    resolution: Resolution,
+    seen_cycles: HashSet<(String, Vec<String>)>,
+}
+
+impl Resolver<'_> {
+    fn resolve_key(&mut self, file: &str, key: &str) -> Option<String> {
+        let id = (file.to_string(), key.to_string());
+        if let Some(cached) = self.memo.get(&id) {
+            return cached.clone();
+        }
+        if let Some(pos) = self.stack.iter().position(|frame| *frame == id) {
+            self.record_cycle(pos);
+            return None;
+        }
+
+        let entry = *self.index.get(&(file, key))?;
+        let raw = entry
+            .value
+            .as_ref()
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string();
+        if entry.references.is_empty() {
+            let plain = entry
+                .resolved_value
+                .as_ref()
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string())
+                .unwrap_or(raw);
+            return Some(plain);
+        }
+
+        self.stack.push(id.clone());
+        let span = entry.span;
+        let value = self.expand(&parse_template(&raw), file, key, span);
+        self.stack.pop();
+
+        self.memo.insert(id, value.clone());
+        value
+    }
+
+    // Expands segments in the scope of `file`; `key` is the entry being resolved
+    fn expand(
+        &mut self,
+        segments: &[Segment],
+        file: &str,
+        key: &str,
+        span: Option<Span>,
+    ) -> Option<String> {
+        let mut out = String::new();
+
+        for segment in segments {
+            match segment {
+                Segment::Literal(text) => out.push_str(text),
+                Segment::Reference { name, default, default_if_empty } => {
+                    let defining_file = self.lookup(file, key, name);
+                    let value = match &defining_file {
+                        Some(defining_file) => Some(self.resolve_key(defining_file, name)?),
+                        None => None,
+                    };
+
+                    let use_default = match &value {
+                        None => true,
+                        Some(v) => *default_if_empty && v.is_empty(),
+                    };
+                    match (use_default, default) {
+                        (false, _) => {
+                            if defining_file.is_some_and(|defining| self.is_secret(&defining, name)) {
+                                self.resolution.from_secrets.insert((file.to_string(), key.to_string()));
+                            }
+                            out.push_str(value.as_deref().unwrap_or_default());
+                        }
+                        (true, Some(default)) => {
+                            out.push_str(&self.expand(&parse_template(default), file, key, span)?);
+                        }
+                        (true, None) => {
+                            let seen = self.resolution.undefined.iter().any(|u| {
+                                u.file == file && u.key == key && u.reference == *name
+                            });
+                            if value.is_none() && !seen {
+                                self.resolution.undefined.push(UndefinedReference {
+                                    file: file.to_string(),
+                                    key: key.to_string(),
+                                    reference: name.clone(),
+                                    span,
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Some(out)
+    }
+
+    // Whether the value of `name` in `file` is a secret or was built from one; `name` is resolved
+    fn is_secret(&self, file: &str, name: &str) -> bool {
+        self.resolution.from_secrets.contains(&(file.to_string(), name.to_string()))
+            || self.index.get(&(file, name)).is_some_and(|entry| entry.is_secret())
+    }
+
+    // Finds the file defining `name` as seen from `key` in `file`
+    fn lookup(&self, file: &str, key: &str, name: &str) -> Option<String> {
+        // A key referring to itself means its previous definition
+        if name != key && self.index.contains_key(&(file, name)) {
+            return Some(file.to_string());
+        }
+        self.chains
+            .get(file)?
+            .iter()
+            .find(|earlier| self.index.contains_key(&(earlier.as_str(), name)))
+            .cloned()
+    }
+
+    fn record_cycle(&mut self, start: usize) {
+        let frames = &self.stack[start..];
+        let file = frames[0].0.clone();
+        let keys: Vec<String> = frames.iter().map(|(_, key)| key.clone()).collect();
+
+        let mut canonical = keys.clone();
+        canonical.sort();
+        if self.seen_cycles.insert((file.clone(), canonical)) {
+            self.resolution.cycles.push(ReferenceCycle { file, keys });
+        }
+    }
+}
@@ -0,0 +1,165 @@
+//! Key name normalization, so `DATABASE_URL` in `.env`, `database.url` in YAML and `databaseUrl`
+//! in JSON are recognized as the same setting. Entries keep their original spelling in `key`; the
+//! analyzer and the config matrix compare `canonical_key`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::NormalizedEntry;
+
+/// `[keys]` in jin.toml.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KeysConfig {
+    /// Compare keys case-insensitively, treating `_`, `__`, `.`, `-` and camelCase humps alike
+    pub normalize: bool,
+    /// Prefixes removed before comparing, e.g. `APP_` or `NEXT_PUBLIC_`
+    pub strip_prefixes: Vec<String>,
+}
+
+impl Default for KeysConfig {
+    fn default() -> Self {
+        Self {
+            normalize: true,
+            strip_prefixes: Vec::new(),
+        }
+    }
+}
+
+/// The form keys are compared in: lowercase segments joined by `.` (`APP_DATABASE_URL` with the
+/// `APP_` prefix stripped becomes `database.url`).
+pub fn canonical_key(key: &str, config: &KeysConfig) -> String {
+    if !config.normalize {
+        return key.to_string();
+    }
+
+    let parts = segments(key);
+    if parts.is_empty() {
+        return key.to_string();
+    }
+    for prefix in &config.strip_prefixes {
+        let prefix = segments(prefix);
+        // A key that is nothing but the prefix keeps it
+        if !prefix.is_empty() && parts.len() > prefix.len() && parts.starts_with(&prefix) {
+            return parts[prefix.len()..].join(".");
+        }
+    }
+    parts.join(".")
+}
+
+/// Sets `canonical_key` on every entry.
+pub fn apply(entries: &mut [NormalizedEntry], config: &KeysConfig) {
+    for entry in entries.iter_mut() {
+        entry.canonical_key = canonical_key(&entry.key, config);
+    }
+}
+
+/// Spells `to` the way `like` is written: same separators and letter case, and any prefix `like`
+/// has beyond its canonical form. Respelling `db.host` as `DATABASE_URL` gives `database.url`;
+/// respelling `APP_DB_HOST` with `APP_` stripped gives `APP_DATABASE_URL`.
+pub fn respell(like: &str, to: &str, config: &KeysConfig) -> String {
+    let model = words(like);
+    let Some(last) = model.last() else {
+        return to.to_string();
+    };
+    let canonical: Vec<String> = canonical_key(like, config).split('.').map(str::to_string).collect();
+    let all = segments(like);
+    let prefix = if all.len() > canonical.len() && all.ends_with(&canonical) {
+        &all[..all.len() - canonical.len()]
+    } else {
+        &[]
+    };
+    let new: Vec<String> = prefix
+        .iter()
+        .cloned()
+        .chain(canonical_key(to, config).split('.').map(str::to_string))
+        .collect();
+
+    // A single word gives no separator to copy; env-style keys use `_`
+    let upper = like.chars().any(char::is_uppercase) && !like.chars().any(char::is_lowercase);
+    let fallback = if upper { "_" } else { "." };
+    let mut spelled = String::new();
+    for (i, word) in new.iter().enumerate() {
+        let style = model.get(i).unwrap_or(last);
+        if i > 0 {
+            spelled.push_str(match model.get(i) {
+                Some(style) => &style.separator,
+                None if model.len() > 1 => &last.separator,
+                None => fallback,
+            });
+        }
+        spelled.push_str(&style.case(word));
+    }
+    spelled
+}
+
+// A word of a key as written, with the separator before it (empty for the first word and camelCase humps)
+struct Word {
+    text: String,
+    separator: String,
+}
+
+impl Word {
+    // `word` in this word's letter case
+    fn case(&self, word: &str) -> String {
+        let mut letters = self.text.chars().filter(|c| c.is_alphabetic());
+        match letters.next() {
+            Some(first) if first.is_uppercase() => {
+                if letters.any(char::is_lowercase) {
+                    let mut chars = word.chars();
+                    chars
+                        .next()
+                        .map(|c| c.to_uppercase().chain(chars).collect())
+                        .unwrap_or_default()
+                } else {
+                    word.to_uppercase()
+                }
+            }
+            _ => word.to_string(),
+        }
+    }
+}
+
+fn segments(key: &str) -> Vec<String> {
+    words(key).into_iter().map(|word| word.text.to_lowercase()).collect()
+}
+
+fn words(key: &str) -> Vec<Word> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut separator = String::new();
+    let mut pending = String::new();
+    let mut prev_lower = false;
+
+    for c in key.chars() {
+        if matches!(c, '_' | '.' | '-') {
+            if !current.is_empty() {
+                words.push(Word {
+                    text: std::mem::take(&mut current),
+                    separator: std::mem::take(&mut separator),
+                });
+            }
+            pending.push(c);
+            prev_lower = false;
+            continue;
+        }
+        // camelCase hump: `databaseUrl` -> `database`, `url`
+        if c.is_uppercase() && prev_lower && !current.is_empty() {
+            words.push(Word {
+                text: std::mem::take(&mut current),
+                separator: std::mem::take(&mut separator),
+            });
+        }
+        if current.is_empty() && !words.is_empty() {
+            separator = std::mem::take(&mut pending);
+        } else if current.is_empty() {
+            pending.clear();
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        current.push(c);
+    }
+
+    if !current.is_empty() {
+        words.push(Word { text: current, separator });
+    }
+    words
+}
@@ -0,0 +1,312 @@
+//! Jin's config discovery, parsing, analysis and export pipeline, with no Tauri dependency.
+//!
+//! ```no_run
+//! # async fn demo() -> Result<(), String> {
+//! let result = jin_core::Scanner::new("./my-project").scan().await?;
+//! println!("{} keys in {} files", result.summary.total_keys, result.summary.total_files);
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod analyzer;
+pub mod baseline;
+pub mod check;
+pub mod compare;
+pub mod config;
+pub mod diagnostics;
+pub mod dotenv;
+pub mod edit;
+pub mod environments;
+pub mod exporter;
+pub mod git;
+pub mod interpolate;
+pub mod keys;
+mod locate;
+pub mod parser;
+pub mod policies;
+pub mod rename;
+pub mod report;
+pub mod sarif;
+pub mod scanner;
+pub mod schema;
+pub mod secrets;
+pub mod sync;
+pub mod textdiff;
+pub mod types;
+pub mod usage;
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+pub use analyzer::analyze_entries;
+pub use baseline::{Baseline, BASELINE_FILE};
+pub use compare::{CompareSide, ConfigDiff};
+pub use config::{JinConfig, CONFIG_FILE};
+pub use diagnostics::Suppression;
+pub use edit::{FileEdit, KeyEdit};
+pub use environments::{Environment, EnvironmentMap};
+pub use exporter::export_env_example;
+pub use git::GitStatus;
+pub use parser::{parse_content, parse_file, Document, ParsedFile};
+pub use rename::{RenameOptions, RenamePlan};
+pub use report::ReportFormat;
+pub use scanner::{scan_directory, JIN_IGNORE_FILE};
+pub use schema::SchemaRegistry;
+pub use secrets::SecretDetector;
+pub use sync::{SyncEdit, SyncOptions};
+pub use types::*;
+
+/// Scans a project root: discovers config files, parses them in parallel and analyzes the result.
+#[derive(Clone)]
+pub struct Scanner {
+    root: String,
+    config: JinConfig,
+    cache: ScanCacheState,
+    baseline: Option<Baseline>,
+}
+
+impl Scanner {
+    /// Creates a scanner with default settings, ignoring any jin.toml.
+    pub fn new(root: impl Into<String>) -> Self {
+        Self {
+            root: root.into(),
+            config: JinConfig::default(),
+            cache: ScanCacheState::new(),
+            baseline: None,
+        }
+    }
+
+    /// Creates a scanner configured from the project's jin.toml, if present.
+    pub fn discover(root: impl Into<String>) -> Result<Self, String> {
+        let root = root.into();
+        let config = JinConfig::discover(&root)?;
+        Ok(Self::new(root).with_config(config))
+    }
+
+    pub fn with_config(mut self, config: JinConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Restricts which files are scanned and how they are parsed.
+    pub fn with_options(mut self, options: ScanOptions) -> Self {
+        self.config.scan = options;
+        self
+    }
+
+    /// Reuses a parse cache across scans so unchanged files are not parsed again.
+    pub fn with_cache(mut self, cache: ScanCacheState) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Leaves issues recorded in `baseline` out of the result, so only new issues are reported.
+    pub fn with_baseline(mut self, baseline: Baseline) -> Self {
+        self.baseline = Some(baseline);
+        self
+    }
+
+    /// Sets the maximum number of files read and parsed at once.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.config.scan.concurrency = concurrency;
+        self
+    }
+
+    pub fn root(&self) -> &str {
+        &self.root
+    }
+
+    pub fn config(&self) -> &JinConfig {
+        &self.config
+    }
+
+    pub async fn scan(&self) -> Result<ScanResult, String> {
+        let path = self.root.clone();
+
+        // Step 1: Discover files
+        let mut files = scan_directory(&path, &self.config.scan)?;
+
+        // Step 2: Create semaphore to limit concurrent file operations
+        let semaphore = Arc::new(Semaphore::new(self.config.scan.concurrency.max(1)));
+        // Compiled once and shared by every file; bad patterns fail the scan instead of being skipped
+        let secrets = Arc::new(SecretDetector::new(&self.config.secrets)?);
+
+        // Step 3: Parse files in parallel with caching and concurrency limiting
+        let mut parse_tasks = Vec::new();
+        for file in &files {
+            let path_clone = path.clone();
+            let file_path = file.path.clone();
+            let full_path = Path::new(&path).join(&file.path);
+            let file_format = file.format.clone();
+            let sem = Arc::clone(&semaphore);
+            let cache = self.cache.clone();
+            let secrets = Arc::clone(&secrets);
+
+            parse_tasks.push(tokio::spawn(async move {
+                // Acquire semaphore permit (limits concurrency)
+                let _permit = sem.acquire().await.unwrap();
+
+                // Try to get file metadata for caching - if it fails, just skip caching
+                let modified_time_opt = match tokio::fs::metadata(&full_path).await {
+                    Ok(metadata) => metadata.modified().ok(),
+                    Err(_) => None,
+                };
+
+                // Check cache if we have a modification time
+                if let Some(modified_time) = modified_time_opt {
+                    if let Some(cached) = cache.get(&full_path.to_string_lossy()) {
+                        // Reuse the results if the file, its format and the secret settings are unchanged
+                        if cached.modified_time == modified_time
+                            && cached.format == file_format
+                            && cached.secrets == secrets.fingerprint()
+                        {
+                            return ParsedFile {
+                                entries: cached.entries,
+                                errors: cached.errors,
+                                suppressions: cached.suppressions,
+                                document: cached.document,
+                            };
+                        }
+                    }
+                }
+
+                // Cache miss, file changed, or no metadata - parse the file
+                let parsed = parse_file(&path_clone, &file_path, &file_format, &secrets).await;
+
+                // Update cache if we have a modification time
+                if let Some(modified_time) = modified_time_opt {
+                    cache.insert(
+                        full_path.to_string_lossy().to_string(),
+                        FileCache {
+                            modified_time,
+                            format: file_format.clone(),
+                            secrets: secrets.fingerprint(),
+                            entries: parsed.entries.clone(),
+                            errors: parsed.errors.clone(),
+                            suppressions: parsed.suppressions.clone(),
+                            document: parsed.document.clone(),
+                        },
+                    );
+                }
+
+                parsed
+            }));
+        }
+
+        // Wait for all parse tasks to complete
+        let mut all_entries: Vec<NormalizedEntry> = Vec::new();
+        let mut parse_errors: Vec<ParseError> = Vec::new();
+        let mut suppressions: HashMap<String, Vec<Suppression>> = HashMap::new();
+        let mut documents: Vec<(String, Document)> = Vec::new();
+
+        for (idx, task) in parse_tasks.into_iter().enumerate() {
+            match task.await {
+                Ok(parsed) => {
+                    files[idx].count = parsed.entries.len();
+                    all_entries.extend(parsed.entries);
+                    parse_errors.extend(parsed.errors);
+                    if !parsed.suppressions.is_empty() {
+                        suppressions.insert(files[idx].path.clone(), parsed.suppressions);
+                    }
+                    if let Some(document) = parsed.document {
+                        documents.push((files[idx].path.clone(), document));
+                    }
+                }
+                Err(e) => {
+                    parse_errors.push(ParseError {
+                        file: files[idx].path.clone(),
+                        message: format!("Task failed: {}", e),
+                        span: None,
+                    });
+                }
+            }
+        }
+
+        // Step 4: Resolve ${VAR} references across env files and line up equivalent key names
+        interpolate::apply(&mut all_entries);
+        keys::apply(&mut all_entries, &self.config.keys);
+
+        // Step 5: Analyze for issues
+        let mut issues = analyze_entries(&all_entries, &self.config);
+        issues.parse_errors = parse_errors;
+
+        // Step 6: Flag secrets in files that are committed or not gitignored. This runs git, so it
+        // runs on the blocking pool
+        let root = PathBuf::from(&path);
+        let file_paths: Vec<String> = files.iter().map(|f| f.path.clone()).collect();
+        let status = tokio::task::spawn_blocking(move || {
+            let file_paths: Vec<&str> = file_paths.iter().map(String::as_str).collect();
+            GitStatus::load(&root, &file_paths)
+        })
+        .await
+        .map_err(|e| format!("Git status check failed: {}", e))?;
+        if let Some(status) = status {
+            issues.exposed_secrets = git::find_exposed_secrets(&all_entries, &status);
+        }
+
+        // Step 7: Match env keys against the variables source code reads
+        if self.config.code.enabled {
+            let index = usage::index(Path::new(&path), &self.config.code, &self.config.scan)?;
+            issues.unused_keys = usage::find_unused(&all_entries, &index, &self.config.code, &self.config.keys);
+            issues.undefined_keys =
+                usage::find_undefined(&all_entries, &index, &self.config.code, &self.config.keys);
+            suppressions.extend(index.suppressions);
+        }
+
+        // Step 8: Validate the documents the structured files were parsed into against their JSON
+        // Schemas. Loading schemas reads files, so this runs on the blocking pool
+        let root = PathBuf::from(&path);
+        let schemas_config = self.config.schemas.clone();
+        issues.schema_violations = tokio::task::spawn_blocking(move || {
+            let mut schemas = SchemaRegistry::new(&root, &schemas_config)?;
+            Ok::<_, String>(
+                documents
+                    .iter()
+                    .flat_map(|(file, document)| schemas.validate_document(file, document))
+                    .collect(),
+            )
+        })
+        .await
+        .map_err(|e| format!("Schema validation failed: {}", e))??;
+
+        // Step 9: Collect every issue into one list, minus the ones silenced inline or in jin.toml
+        // and the ones already in the baseline
+        let list = diagnostics::apply(
+            &mut issues,
+            &all_entries,
+            &self.config,
+            &suppressions,
+            self.baseline.as_ref(),
+        );
+
+        // Step 10: Group files into environments
+        let file_kinds: Vec<(String, bool)> = files
+            .iter()
+            .map(|f| (f.path.clone(), f.format == SourceFormat::Env))
+            .collect();
+        let environments = EnvironmentMap::build(&file_kinds, &self.config.environments).environments;
+
+        // Step 11: Generate summary
+        let unique_keys: HashSet<&str> = all_entries.iter().map(|e| e.canonical_key.as_str()).collect();
+
+        let summary = ScanSummary {
+            total_files: files.len(),
+            total_keys: all_entries.len(),
+            unique_keys: unique_keys.len(),
+            suppressed: list.suppressed,
+            baselined: list.baselined,
+        };
+
+        Ok(ScanResult {
+            root: path,
+            files,
+            environments,
+            entries: all_entries,
+            issues,
+            diagnostics: list.issues,
+            summary,
+        })
+    }
+}
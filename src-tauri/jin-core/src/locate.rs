@@ -0,0 +1,285 @@
+use std::collections::HashMap;
+use std::ops::Range;
+use yaml_rust2::parser::{Event, Parser};
+
+use crate::types::Span;
+
+// Source positions of flattened keys, keyed by the same dotted paths the parser produces.
+// Only called on documents that already parsed successfully, so malformed input just ends the walk.
+
+/// Converts a byte offset into a 1-based line and column (columns count characters).
+pub fn span_at(content: &str, offset: usize) -> Span {
+    let offset = offset.min(content.len());
+    let before = &content[..offset];
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+
+    Span {
+        line: before.matches('\n').count() + 1,
+        column: before[line_start..].chars().count() + 1,
+    }
+}
+
+fn join_key(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", prefix, key)
+    }
+}
+
+pub fn json_key_spans(content: &str) -> HashMap<String, Span> {
+    json_layout(content)
+        .members
+        .into_iter()
+        .map(|(key, member)| (key, span_at(content, member.key.start)))
+        .collect()
+}
+
+/// Byte ranges of an object member: the quoted key and its value.
+#[derive(Debug, Clone)]
+pub(crate) struct JsonMember {
+    pub key: Range<usize>,
+    pub value: Range<usize>,
+}
+
+/// Byte offsets of an object's braces, and the paths of its members in order.
+#[derive(Debug, Clone)]
+pub(crate) struct JsonObject {
+    pub open: usize,
+    pub close: usize,
+    pub members: Vec<String>,
+}
+
+/// Members and objects of a JSON document by dotted path; the root object's path is empty.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct JsonLayout {
+    pub members: HashMap<String, JsonMember>,
+    pub objects: HashMap<String, JsonObject>,
+}
+
+pub(crate) fn json_layout(content: &str) -> JsonLayout {
+    let mut locator = JsonLocator {
+        src: content,
+        pos: 0,
+        layout: JsonLayout::default(),
+    };
+    locator.value(Some(""));
+    locator.layout
+}
+
+struct JsonLocator<'a> {
+    src: &'a str,
+    pos: usize,
+    layout: JsonLayout,
+}
+
+impl JsonLocator<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.src.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    // `path` is None below arrays, which the parser keeps as values instead of flattening
+    fn value(&mut self, path: Option<&str>) {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'{') => self.object(path),
+            Some(b'[') => self.array(),
+            Some(b'"') => {
+                self.string();
+            }
+            Some(_) => {
+                while !matches!(self.peek(), None | Some(b',' | b'}' | b']' | b' ' | b'\t' | b'\n' | b'\r')) {
+                    self.pos += 1;
+                }
+            }
+            None => {}
+        }
+    }
+
+    fn object(&mut self, path: Option<&str>) {
+        let open = self.pos;
+        let mut members = Vec::new();
+        self.pos += 1;
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b'}') => {
+                    if let Some(path) = path {
+                        let close = self.pos;
+                        self.layout
+                            .objects
+                            .insert(path.to_string(), JsonObject { open, close, members });
+                    }
+                    self.pos += 1;
+                    return;
+                }
+                Some(b',') => self.pos += 1,
+                Some(b'"') => {
+                    let key_start = self.pos;
+                    let Some(key) = self.string() else { return };
+                    let key_end = self.pos;
+                    self.skip_whitespace();
+                    if self.peek() != Some(b':') {
+                        return;
+                    }
+                    self.pos += 1;
+
+                    let full_key = path.map(|prefix| join_key(prefix, &key));
+                    self.skip_whitespace();
+                    let value_start = self.pos;
+                    self.value(full_key.as_deref());
+                    if let Some(full_key) = full_key {
+                        self.layout.members.insert(
+                            full_key.clone(),
+                            JsonMember {
+                                key: key_start..key_end,
+                                value: value_start..self.pos,
+                            },
+                        );
+                        members.push(full_key);
+                    }
+                }
+                _ => return,
+            }
+        }
+    }
+
+    fn array(&mut self) {
+        self.pos += 1;
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b']') => {
+                    self.pos += 1;
+                    return;
+                }
+                Some(b',') => self.pos += 1,
+                Some(_) => {
+                    let before = self.pos;
+                    self.value(None);
+                    if self.pos == before {
+                        return;
+                    }
+                }
+                None => return,
+            }
+        }
+    }
+
+    // Consumes a string literal and returns its decoded contents
+    fn string(&mut self) -> Option<String> {
+        let start = self.pos;
+        self.pos += 1;
+        while let Some(byte) = self.peek() {
+            match byte {
+                b'\\' => self.pos += 2,
+                b'"' => {
+                    self.pos += 1;
+                    return serde_json::from_str(&self.src[start..self.pos]).ok();
+                }
+                _ => self.pos += 1,
+            }
+        }
+        None
+    }
+}
+
+pub fn yaml_key_spans(content: &str) -> HashMap<String, Span> {
+    enum Frame {
+        // `path` is None below sequences, which the parser keeps as values instead of flattening
+        Mapping { path: Option<String>, key: Option<String> },
+        Sequence,
+    }
+
+    // A new node is either a mapping value (consuming the pending key) or a sequence item
+    fn child_path(stack: &mut [Frame]) -> Option<String> {
+        match stack.last_mut() {
+            None => Some(String::new()),
+            Some(Frame::Sequence) => None,
+            Some(Frame::Mapping { path, key }) => {
+                let key = key.take()?;
+                path.as_deref().map(|prefix| join_key(prefix, &key))
+            }
+        }
+    }
+
+    let mut spans = HashMap::new();
+    let mut stack: Vec<Frame> = Vec::new();
+    let mut parser = Parser::new_from_str(content);
+
+    while let Ok((event, marker)) = parser.next_token() {
+        match event {
+            Event::StreamEnd | Event::DocumentEnd => break,
+            Event::MappingStart(..) => {
+                let path = child_path(&mut stack);
+                stack.push(Frame::Mapping { path, key: None });
+            }
+            Event::SequenceStart(..) => {
+                child_path(&mut stack);
+                stack.push(Frame::Sequence);
+            }
+            Event::MappingEnd | Event::SequenceEnd => {
+                stack.pop();
+            }
+            Event::Scalar(value, ..) => match stack.last_mut() {
+                Some(Frame::Mapping { path, key }) if key.is_none() => {
+                    if let Some(prefix) = path {
+                        spans.insert(
+                            join_key(prefix, &value),
+                            Span {
+                                line: marker.line(),
+                                column: marker.col() + 1,
+                            },
+                        );
+                    }
+                    *key = Some(value);
+                }
+                Some(Frame::Mapping { key, .. }) => *key = None,
+                _ => {}
+            },
+            Event::Alias(_) => {
+                if let Some(Frame::Mapping { key, .. }) = stack.last_mut() {
+                    *key = None;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    spans
+}
+
+pub fn toml_key_spans(content: &str) -> HashMap<String, Span> {
+    fn walk(
+        table: &dyn toml_edit::TableLike,
+        prefix: &str,
+        content: &str,
+        spans: &mut HashMap<String, Span>,
+    ) {
+        for (key, item) in table.iter() {
+            let full_key = join_key(prefix, key);
+            let start = table
+                .get_key_value(key)
+                .and_then(|(key, _)| key.span())
+                .map(|range| range.start);
+            if let Some(start) = start {
+                spans.insert(full_key.clone(), span_at(content, start));
+            }
+            if let Some(child) = item.as_table_like() {
+                walk(child, &full_key, content, spans);
+            }
+        }
+    }
+
+    let mut spans = HashMap::new();
+    if let Ok(document) = toml_edit::ImDocument::parse(content) {
+        walk(document.as_table(), "", content, &mut spans);
+    }
+    spans
+}
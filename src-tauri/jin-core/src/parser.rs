@@ -0,0 +1,419 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::diagnostics::{self, Suppression};
+use crate::dotenv::{self, Quote};
+use crate::interpolate;
+use crate::keys::{canonical_key, KeysConfig};
+use crate::secrets::SecretDetector;
+use crate::locate::{json_key_spans, span_at, toml_key_spans, yaml_key_spans};
+use crate::schema::modeline_schema;
+use crate::types::{InferredType, NormalizedEntry, ParseError, SourceFormat, Span};
+
+/// Entries parsed from one file, plus any errors. Env files keep their valid lines when others
+/// are malformed; the structured formats fail as a whole.
+#[derive(Debug, Clone, Default)]
+pub struct ParsedFile {
+    pub entries: Vec<NormalizedEntry>,
+    pub errors: Vec<ParseError>,
+    /// `jin-ignore` comments in the file
+    pub suppressions: Vec<Suppression>,
+    /// The YAML, JSON or TOML document the entries were flattened from, for schema validation
+    pub document: Option<Document>,
+}
+
+/// A YAML, JSON or TOML file as one JSON document, before it is flattened into entries.
+#[derive(Debug, Clone)]
+pub struct Document {
+    pub value: serde_json::Value,
+    /// Position of each key, by dotted path
+    pub spans: HashMap<String, Span>,
+    /// Schema named in a leading `# yaml-language-server: $schema=` or `#:schema` comment
+    pub modeline: Option<String>,
+}
+
+impl Document {
+    /// Parses `content`; `None` for env files and for content that doesn't parse.
+    pub fn parse(content: &str, format: &SourceFormat) -> Option<Self> {
+        let value = match format {
+            SourceFormat::Env => return None,
+            SourceFormat::Yaml => serde_yaml::from_str(content).ok()?,
+            SourceFormat::Json => serde_json::from_str(content).ok()?,
+            SourceFormat::Toml => toml_to_json(&toml::from_str::<toml::Value>(content).ok()?),
+        };
+        let spans = match format {
+            SourceFormat::Yaml => yaml_key_spans(content),
+            SourceFormat::Json => json_key_spans(content),
+            _ => toml_key_spans(content),
+        };
+        Some(Self::new(value, spans, content))
+    }
+
+    fn new(value: serde_json::Value, spans: HashMap<String, Span>, content: &str) -> Self {
+        Self {
+            value,
+            spans,
+            modeline: modeline_schema(content),
+        }
+    }
+}
+
+pub async fn parse_file(
+    root_path: &str,
+    relative_path: &str,
+    format: &SourceFormat,
+    secrets: &SecretDetector,
+) -> ParsedFile {
+    let full_path = Path::new(root_path).join(relative_path);
+
+    match tokio::fs::read_to_string(&full_path).await {
+        Ok(content) => parse_content(&content, relative_path, format, secrets),
+        Err(e) => ParsedFile {
+            errors: vec![ParseError {
+                file: relative_path.to_string(),
+                message: format!("Failed to read file: {}", e),
+                span: None,
+            }],
+            ..ParsedFile::default()
+        },
+    }
+}
+
+/// Parses in-memory `content` as if it were read from `relative_path`.
+pub fn parse_content(
+    content: &str,
+    relative_path: &str,
+    format: &SourceFormat,
+    secrets: &SecretDetector,
+) -> ParsedFile {
+    let structured = match format {
+        SourceFormat::Env => {
+            let (entries, errors) = parse_env(content, relative_path, secrets);
+            return ParsedFile {
+                entries,
+                errors,
+                suppressions: diagnostics::suppressions(content),
+                document: None,
+            };
+        }
+        SourceFormat::Yaml => parse_yaml(content, relative_path, secrets),
+        SourceFormat::Json => parse_json(content, relative_path, secrets),
+        SourceFormat::Toml => parse_toml(content, relative_path, secrets),
+    };
+
+    let (entries, errors, document) = match structured {
+        Ok((entries, document)) => (entries, Vec::new(), document),
+        Err(error) => (Vec::new(), vec![error], None),
+    };
+    ParsedFile {
+        entries,
+        errors,
+        suppressions: diagnostics::suppressions(content),
+        document,
+    }
+}
+
+fn parse_env(
+    content: &str,
+    file_path: &str,
+    secrets: &SecretDetector,
+) -> (Vec<NormalizedEntry>, Vec<ParseError>) {
+    let parsed = dotenv::parse(content);
+
+    let entries = parsed
+        .entries
+        .into_iter()
+        .map(|entry| {
+            // Single-quoted and backtick values are taken literally
+            let interpolated = matches!(entry.quote, Quote::None | Quote::Double);
+            let references = if interpolated {
+                interpolate::references(&entry.value)
+            } else {
+                Vec::new()
+            };
+            // Values with references are resolved across files once the scan completes
+            let resolved_value = if interpolated && references.is_empty() {
+                let unescaped = interpolate::unescape(&entry.value);
+                (unescaped != entry.value).then_some(serde_json::Value::String(unescaped))
+            } else {
+                None
+            };
+
+            let mut normalized = create_entry(
+                entry.key,
+                serde_json::Value::String(entry.value),
+                file_path.to_string(),
+                SourceFormat::Env,
+                Some(entry.span),
+                secrets,
+            );
+            normalized.references = references;
+            normalized.resolved_value = resolved_value;
+            normalized
+        })
+        .collect();
+
+    let errors = parsed
+        .errors
+        .into_iter()
+        .map(|error| ParseError {
+            file: file_path.to_string(),
+            message: format!("Env parse error: {}", error.message),
+            span: Some(error.span),
+        })
+        .collect();
+
+    (entries, errors)
+}
+
+fn parse_yaml(
+    content: &str,
+    file_path: &str,
+    secrets: &SecretDetector,
+) -> Result<(Vec<NormalizedEntry>, Option<Document>), ParseError> {
+    let yaml_value: serde_yaml::Value = serde_yaml::from_str(content).map_err(|e| ParseError {
+        file: file_path.to_string(),
+        message: format!("YAML parse error: {}", e),
+        span: e.location().map(|location| Span {
+            line: location.line(),
+            column: location.column(),
+        }),
+    })?;
+
+    let spans = yaml_key_spans(content);
+    let flattened = flatten_value("", &yaml_value);
+    let entries = flattened
+        .into_iter()
+        .map(|(key, value)| {
+            let span = spans.get(&key).copied();
+            create_entry(key, value, file_path.to_string(), SourceFormat::Yaml, span, secrets)
+        })
+        .collect();
+
+    // Converted from the parsed value, so the text isn't parsed twice. Documents JSON can't
+    // represent, e.g. with non-string keys, aren't validated
+    let document = serde_yaml::from_value(yaml_value).ok();
+    Ok((entries, document.map(|value| Document::new(value, spans, content))))
+}
+
+fn parse_json(
+    content: &str,
+    file_path: &str,
+    secrets: &SecretDetector,
+) -> Result<(Vec<NormalizedEntry>, Option<Document>), ParseError> {
+    let json_value: serde_json::Value = serde_json::from_str(content).map_err(|e| ParseError {
+        file: file_path.to_string(),
+        message: format!("JSON parse error: {}", e),
+        span: Some(Span {
+            line: e.line(),
+            column: e.column(),
+        }),
+    })?;
+
+    let spans = json_key_spans(content);
+    let flattened = flatten_json_value("", &json_value);
+    let entries = flattened
+        .into_iter()
+        .map(|(key, value)| {
+            let span = spans.get(&key).copied();
+            create_entry(key, value, file_path.to_string(), SourceFormat::Json, span, secrets)
+        })
+        .collect();
+
+    Ok((entries, Some(Document::new(json_value, spans, content))))
+}
+
+fn parse_toml(
+    content: &str,
+    file_path: &str,
+    secrets: &SecretDetector,
+) -> Result<(Vec<NormalizedEntry>, Option<Document>), ParseError> {
+    let toml_value: toml::Value = toml::from_str(content).map_err(|e| ParseError {
+        file: file_path.to_string(),
+        message: format!("TOML parse error: {}", e),
+        span: e.span().map(|range| span_at(content, range.start)),
+    })?;
+
+    let spans = toml_key_spans(content);
+    let flattened = flatten_toml_value("", &toml_value);
+    let entries = flattened
+        .into_iter()
+        .map(|(key, value)| {
+            let span = spans.get(&key).copied();
+            create_entry(key, value, file_path.to_string(), SourceFormat::Toml, span, secrets)
+        })
+        .collect();
+
+    Ok((entries, Some(Document::new(toml_to_json(&toml_value), spans, content))))
+}
+
+// Flatten YAML value recursively
+fn flatten_value(prefix: &str, value: &serde_yaml::Value) -> HashMap<String, serde_json::Value> {
+    let mut result = HashMap::new();
+
+    match value {
+        serde_yaml::Value::Mapping(map) => {
+            for (k, v) in map {
+                if let Some(key_str) = k.as_str() {
+                    let new_key = if prefix.is_empty() {
+                        key_str.to_string()
+                    } else {
+                        format!("{}.{}", prefix, key_str)
+                    };
+
+                    if let serde_yaml::Value::Mapping(_) = v {
+                        result.extend(flatten_value(&new_key, v));
+                    } else {
+                        // Convert YAML value to JSON value
+                        let json_val = yaml_to_json(v);
+                        result.insert(new_key, json_val);
+                    }
+                }
+            }
+        }
+        _ => {
+            if !prefix.is_empty() {
+                result.insert(prefix.to_string(), yaml_to_json(value));
+            }
+        }
+    }
+
+    result
+}
+
+// Flatten JSON value recursively
+fn flatten_json_value(prefix: &str, value: &serde_json::Value) -> HashMap<String, serde_json::Value> {
+    let mut result = HashMap::new();
+
+    match value {
+        serde_json::Value::Object(map) => {
+            for (k, v) in map {
+                let new_key = if prefix.is_empty() {
+                    k.clone()
+                } else {
+                    format!("{}.{}", prefix, k)
+                };
+
+                if v.is_object() {
+                    result.extend(flatten_json_value(&new_key, v));
+                } else {
+                    result.insert(new_key, v.clone());
+                }
+            }
+        }
+        _ => {
+            if !prefix.is_empty() {
+                result.insert(prefix.to_string(), value.clone());
+            }
+        }
+    }
+
+    result
+}
+
+// Flatten TOML value recursively
+fn flatten_toml_value(prefix: &str, value: &toml::Value) -> HashMap<String, serde_json::Value> {
+    let mut result = HashMap::new();
+
+    match value {
+        toml::Value::Table(map) => {
+            for (k, v) in map {
+                let new_key = if prefix.is_empty() {
+                    k.clone()
+                } else {
+                    format!("{}.{}", prefix, k)
+                };
+
+                if matches!(v, toml::Value::Table(_)) {
+                    result.extend(flatten_toml_value(&new_key, v));
+                } else {
+                    result.insert(new_key, toml_to_json(v));
+                }
+            }
+        }
+        _ => {
+            if !prefix.is_empty() {
+                result.insert(prefix.to_string(), toml_to_json(value));
+            }
+        }
+    }
+
+    result
+}
+
+// Helper to convert YAML to JSON
+fn yaml_to_json(value: &serde_yaml::Value) -> serde_json::Value {
+    match value {
+        serde_yaml::Value::Null => serde_json::Value::Null,
+        serde_yaml::Value::Bool(b) => serde_json::Value::Bool(*b),
+        serde_yaml::Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                serde_json::Value::Number(i.into())
+            } else if let Some(f) = n.as_f64() {
+                serde_json::Value::Number(
+                    serde_json::Number::from_f64(f).unwrap_or(serde_json::Number::from(0)),
+                )
+            } else {
+                serde_json::Value::Null
+            }
+        }
+        serde_yaml::Value::String(s) => serde_json::Value::String(s.clone()),
+        _ => serde_json::Value::String(format!("{:?}", value)),
+    }
+}
+
+// Helper to convert TOML to JSON
+fn toml_to_json(value: &toml::Value) -> serde_json::Value {
+    match value {
+        toml::Value::String(s) => serde_json::Value::String(s.clone()),
+        toml::Value::Integer(i) => serde_json::Value::Number((*i).into()),
+        toml::Value::Float(f) => {
+            serde_json::Value::Number(serde_json::Number::from_f64(*f).unwrap_or(0.into()))
+        }
+        toml::Value::Boolean(b) => serde_json::Value::Bool(*b),
+        toml::Value::Datetime(dt) => serde_json::Value::String(dt.to_string()),
+        toml::Value::Array(arr) => {
+            serde_json::Value::Array(arr.iter().map(toml_to_json).collect())
+        }
+        toml::Value::Table(table) => serde_json::Value::Object(
+            table.iter().map(|(k, v)| (k.clone(), toml_to_json(v))).collect(),
+        ),
+    }
+}
+
+fn create_entry(
+    key: String,
+    value: serde_json::Value,
+    source_file: String,
+    source_format: SourceFormat,
+    span: Option<Span>,
+    secrets: &SecretDetector,
+) -> NormalizedEntry {
+    let inferred_type = infer_type(&value);
+    let secret = secrets.detect(&key, &value);
+
+    NormalizedEntry {
+        canonical_key: canonical_key(&key, &KeysConfig::default()),
+        key,
+        value: Some(value),
+        source_file,
+        source_format,
+        inferred_type,
+        secret_confidence: secret.as_ref().map(|s| s.confidence).unwrap_or(0.0),
+        secret_rule: secret.map(|s| s.rule),
+        span,
+        references: Vec::new(),
+        resolved_value: None,
+        references_secret: false,
+    }
+}
+
+fn infer_type(value: &serde_json::Value) -> InferredType {
+    match value {
+        serde_json::Value::Null => InferredType::Null,
+        serde_json::Value::Bool(_) => InferredType::Boolean,
+        serde_json::Value::Number(_) => InferredType::Number,
+        serde_json::Value::String(_) => InferredType::String,
+        _ => InferredType::Unknown,
+    }
+}
@@ -0,0 +1,393 @@
+//! User-defined rules from `[[policies]]` in jin.toml, checked against every entry they select.
+//!
+//! ```toml
+//! [[policies]]
+//! id = "port-range"
+//! keys = ["PORT", "*.port"]
+//! type = "integer"
+//! min = 1024
+//! max = 65535
+//!
+//! [[policies]]
+//! id = "no-debug-in-production"
+//! keys = ["DEBUG"]
+//! environments = ["production"]
+//! equals = false
+//! message = "{key} is enabled in {file}"
+//! ```
+//!
+//! Every assertion set on a policy must hold. Values from `.env` files are strings, so `type`,
+//! `min`/`max` and `equals` check what the value parses as; structured files are checked by their
+//! actual type. Empty values are left to the missing-keys check.
+
+use globset::{Glob, GlobMatcher, GlobSet};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::OnceLock;
+
+use crate::check::Severity;
+use crate::environments::EnvironmentMap;
+use crate::scanner::build_glob_set;
+use crate::types::{NormalizedEntry, PolicyViolation, SourceFormat};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValueType {
+    String,
+    Integer,
+    Number,
+    Boolean,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValueFormat {
+    Url,
+    Email,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyCase {
+    ScreamingSnakeCase,
+    SnakeCase,
+    CamelCase,
+    KebabCase,
+}
+
+/// One `[[policies]]` entry: selectors, then assertions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Policy {
+    pub id: String,
+    #[serde(default = "default_severity")]
+    pub severity: Severity,
+    /// Overrides the generated message; `{key}`, `{value}` and `{file}` are filled in
+    #[serde(default)]
+    pub message: Option<String>,
+
+    /// Globs over key names, matched against the key as written and its canonical form
+    #[serde(default)]
+    pub keys: Vec<String>,
+    /// Globs over file paths, like `[scan] include`
+    #[serde(default)]
+    pub files: Vec<String>,
+    /// Only files in these environments
+    #[serde(default)]
+    pub environments: Vec<String>,
+
+    #[serde(default, rename = "type")]
+    pub value_type: Option<ValueType>,
+    #[serde(default)]
+    pub min: Option<f64>,
+    #[serde(default)]
+    pub max: Option<f64>,
+    #[serde(default)]
+    pub equals: Option<serde_json::Value>,
+    #[serde(default)]
+    pub one_of: Vec<serde_json::Value>,
+    /// Regular expression the whole value must match
+    #[serde(default)]
+    pub pattern: Option<String>,
+    #[serde(default)]
+    pub format: Option<ValueFormat>,
+    /// Naming convention for every segment of the key
+    #[serde(default)]
+    pub key_case: Option<KeyCase>,
+}
+
+fn default_severity() -> Severity {
+    Severity::Error
+}
+
+/// Checks that every policy compiles and asserts something; called when jin.toml is loaded.
+pub(crate) fn validate(policies: &[Policy]) -> Result<(), String> {
+    for policy in policies {
+        CompiledPolicy::new(policy)?;
+        // A policy without assertions passes every entry, which is never what was meant
+        let asserts = policy.value_type.is_some()
+            || policy.min.is_some()
+            || policy.max.is_some()
+            || policy.equals.is_some()
+            || !policy.one_of.is_empty()
+            || policy.pattern.is_some()
+            || policy.format.is_some()
+            || policy.key_case.is_some();
+        if !asserts {
+            return Err(format!(
+                "policies '{}': set at least one of type, min, max, equals, one_of, pattern, format or key_case",
+                policy.id
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Runs every enabled policy against `entries`.
+pub fn check(entries: &[NormalizedEntry], policies: &[Policy], map: &EnvironmentMap) -> Vec<PolicyViolation> {
+    // Policies were validated when the config was loaded
+    let compiled: Vec<CompiledPolicy> = policies
+        .iter()
+        .filter(|p| p.severity != Severity::Off)
+        .filter_map(|p| CompiledPolicy::new(p).ok())
+        .collect();
+
+    let mut violations = Vec::new();
+    for entry in entries {
+        for policy in &compiled {
+            if !policy.selects(entry, map) {
+                continue;
+            }
+            if let Some(failure) = policy.failure(entry) {
+                violations.push(PolicyViolation {
+                    policy: policy.source.id.clone(),
+                    severity: policy.source.severity,
+                    file: entry.source_file.clone(),
+                    key: entry.key.clone(),
+                    message: policy.message(entry, failure),
+                    span: entry.span,
+                });
+            }
+        }
+    }
+    violations
+}
+
+struct CompiledPolicy<'a> {
+    source: &'a Policy,
+    keys: Vec<GlobMatcher>,
+    files: Option<GlobSet>,
+    pattern: Option<Regex>,
+}
+
+impl<'a> CompiledPolicy<'a> {
+    fn new(source: &'a Policy) -> Result<Self, String> {
+        let context = |e: String| format!("policies '{}': {}", source.id, e);
+
+        let keys = source
+            .keys
+            .iter()
+            .map(|pattern| {
+                Glob::new(pattern)
+                    .map(|glob| glob.compile_matcher())
+                    .map_err(|e| context(format!("invalid key glob '{}': {}", pattern, e)))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let files = if source.files.is_empty() {
+            None
+        } else {
+            Some(build_glob_set(&source.files).map_err(context)?)
+        };
+        let pattern = source
+            .pattern
+            .as_ref()
+            // Anchored, so the pattern describes the whole value
+            .map(|pattern| Regex::new(&format!("^(?:{})$", pattern)))
+            .transpose()
+            .map_err(|e| context(e.to_string()))?;
+
+        Ok(Self {
+            source,
+            keys,
+            files,
+            pattern,
+        })
+    }
+
+    fn selects(&self, entry: &NormalizedEntry, map: &EnvironmentMap) -> bool {
+        let key_matches = self.keys.is_empty()
+            || self
+                .keys
+                .iter()
+                .any(|glob| glob.is_match(&entry.key) || glob.is_match(&entry.canonical_key));
+        let file_matches = self
+            .files
+            .as_ref()
+            .map(|files| files.is_match(Path::new(&entry.source_file)))
+            .unwrap_or(true);
+        let environment_matches = self.source.environments.is_empty()
+            || map
+                .environment_of(&entry.source_file)
+                .map(|env| self.source.environments.iter().any(|e| e == env))
+                .unwrap_or(false);
+
+        key_matches && file_matches && environment_matches
+    }
+
+    // Describes the first assertion the entry fails
+    fn failure(&self, entry: &NormalizedEntry) -> Option<String> {
+        let policy = self.source;
+
+        if let Some(case) = policy.key_case {
+            if !entry.key.split('.').all(|segment| key_case_matches(case, segment)) {
+                return Some(format!("key must be {}", key_case_name(case)));
+            }
+        }
+
+        let value = entry.resolved_value.as_ref().or(entry.value.as_ref())?;
+        if value.is_null() || value.as_str() == Some("") {
+            return None;
+        }
+        let lenient = entry.source_format == SourceFormat::Env;
+
+        if let Some(value_type) = policy.value_type {
+            if !has_type(value, value_type, lenient) {
+                return Some(format!("must be {}", type_name(value_type)));
+            }
+        }
+        if policy.min.is_some() || policy.max.is_some() {
+            let Some(number) = as_number(value, lenient) else {
+                return Some("must be a number".to_string());
+            };
+            let below = policy.min.map(|min| number < min).unwrap_or(false);
+            let above = policy.max.map(|max| number > max).unwrap_or(false);
+            if below || above {
+                return Some(match (policy.min, policy.max) {
+                    (Some(min), Some(max)) => format!("must be between {} and {}", min, max),
+                    (Some(min), None) => format!("must be at least {}", min),
+                    (None, max) => format!("must be at most {}", max.unwrap_or_default()),
+                });
+            }
+        }
+        if let Some(expected) = &policy.equals {
+            if !values_equal(value, expected, lenient) {
+                return Some(format!("must be {}", expected));
+            }
+        }
+        if !policy.one_of.is_empty()
+            && !policy.one_of.iter().any(|expected| values_equal(value, expected, lenient))
+        {
+            let allowed: Vec<String> = policy.one_of.iter().map(|v| v.to_string()).collect();
+            return Some(format!("must be one of {}", allowed.join(", ")));
+        }
+
+        let text = value_text(value);
+        if let Some(pattern) = &self.pattern {
+            if !pattern.is_match(&text) {
+                return Some(format!("must match {}", policy.pattern.as_deref().unwrap_or_default()));
+            }
+        }
+        if let Some(format) = policy.format {
+            if !format_matches(format, &text) {
+                return Some(match format {
+                    ValueFormat::Url => "must be a valid URL".to_string(),
+                    ValueFormat::Email => "must be a valid email address".to_string(),
+                });
+            }
+        }
+        None
+    }
+
+    fn message(&self, entry: &NormalizedEntry, failure: String) -> String {
+        let Some(template) = &self.source.message else {
+            return format!("{} {}", entry.key, failure);
+        };
+        let value = if entry.is_secret() {
+            crate::analyzer::MASKED_VALUE.to_string()
+        } else {
+            entry.value.as_ref().map(value_text).unwrap_or_default()
+        };
+        template
+            .replace("{key}", &entry.key)
+            .replace("{file}", &entry.source_file)
+            .replace("{value}", &value)
+    }
+}
+
+fn value_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn as_number(value: &serde_json::Value, lenient: bool) -> Option<f64> {
+    match value {
+        serde_json::Value::Number(n) => n.as_f64(),
+        serde_json::Value::String(s) if lenient => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn as_bool(value: &serde_json::Value, lenient: bool) -> Option<bool> {
+    match value {
+        serde_json::Value::Bool(b) => Some(*b),
+        serde_json::Value::String(s) if lenient => match s.trim().to_lowercase().as_str() {
+            "true" => Some(true),
+            "false" => Some(false),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn has_type(value: &serde_json::Value, value_type: ValueType, lenient: bool) -> bool {
+    match value_type {
+        ValueType::String => value.is_string(),
+        ValueType::Number => as_number(value, lenient).is_some(),
+        ValueType::Integer => match value {
+            serde_json::Value::Number(n) => n.is_i64() || n.is_u64(),
+            serde_json::Value::String(s) if lenient => s.trim().parse::<i64>().is_ok(),
+            _ => false,
+        },
+        ValueType::Boolean => as_bool(value, lenient).is_some(),
+    }
+}
+
+fn values_equal(value: &serde_json::Value, expected: &serde_json::Value, lenient: bool) -> bool {
+    match expected {
+        serde_json::Value::Bool(b) => as_bool(value, lenient) == Some(*b),
+        serde_json::Value::Number(n) => as_number(value, lenient) == n.as_f64(),
+        serde_json::Value::String(s) => value_text(value) == *s,
+        other => value == other,
+    }
+}
+
+fn type_name(value_type: ValueType) -> &'static str {
+    match value_type {
+        ValueType::String => "a string",
+        ValueType::Integer => "an integer",
+        ValueType::Number => "a number",
+        ValueType::Boolean => "a boolean",
+    }
+}
+
+fn key_case_name(case: KeyCase) -> &'static str {
+    match case {
+        KeyCase::ScreamingSnakeCase => "SCREAMING_SNAKE_CASE",
+        KeyCase::SnakeCase => "snake_case",
+        KeyCase::CamelCase => "camelCase",
+        KeyCase::KebabCase => "kebab-case",
+    }
+}
+
+fn key_case_matches(case: KeyCase, segment: &str) -> bool {
+    static PATTERNS: OnceLock<[Regex; 4]> = OnceLock::new();
+    let patterns = PATTERNS.get_or_init(|| {
+        [
+            r"^[A-Z][A-Z0-9]*(?:_[A-Z0-9]+)*$",
+            r"^[a-z][a-z0-9]*(?:_[a-z0-9]+)*$",
+            r"^[a-z][a-zA-Z0-9]*$",
+            r"^[a-z][a-z0-9]*(?:-[a-z0-9]+)*$",
+        ]
+        .map(|pattern| Regex::new(pattern).expect("key case pattern"))
+    });
+    // Array indexes in flattened keys are not names
+    if segment.chars().all(|c| c.is_ascii_digit()) {
+        return true;
+    }
+    patterns[case as usize].is_match(segment)
+}
+
+fn format_matches(format: ValueFormat, text: &str) -> bool {
+    static PATTERNS: OnceLock<[Regex; 2]> = OnceLock::new();
+    let patterns = PATTERNS.get_or_init(|| {
+        [
+            r"^[A-Za-z][A-Za-z0-9+.-]*://[^\s/?#]+(?:[/?#]\S*)?$",
+            r"^[^@\s]+@[^@\s]+\.[^@\s.]+$",
+        ]
+        .map(|pattern| Regex::new(pattern).expect("value format pattern"))
+    });
+    patterns[format as usize].is_match(text)
+}
@@ -0,0 +1,236 @@
+//! Bulk rename: one key renamed in every scanned file that defines it, optionally following the
+//! key into other formats (`DB_HOST` in `.env`, `db.host` in YAML). The whole change set is planned
+//! first, so it can be previewed before anything is written.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::analyzer::MASKED_VALUE;
+use crate::config::JinConfig;
+use crate::edit::{self, is_below, FileEdit, KeyEdit};
+use crate::keys::{canonical_key, respell, KeysConfig};
+use crate::parser::parse_content;
+use crate::secrets::SecretDetector;
+use crate::textdiff::unified_diff;
+use crate::types::{NormalizedEntry, ScanResult, SourceFormat};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameOptions {
+    /// Key to rename, as spelled in the files (`DB_HOST`, `db.host`, or a table path like `db`)
+    pub from: String,
+    pub to: String,
+    /// Also rename keys that name the same setting in another spelling (see `keys::canonical_key`),
+    /// writing the new name in each file's own style
+    pub cross_format: bool,
+    /// Files to rename in, relative to the scan root; every scanned file when empty
+    pub files: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenamedKey {
+    pub from: String,
+    pub to: String,
+}
+
+/// The planned change to one file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameChange {
+    pub renamed: Vec<RenamedKey>,
+    /// The edit itself; its diff has secret values masked
+    pub edit: FileEdit,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenamePlan {
+    pub changes: Vec<RenameChange>,
+    /// Files that define the key but can't be renamed, e.g. because the new name is taken
+    pub conflicts: Vec<String>,
+}
+
+/// Plans the rename in every file that defines the key. Nothing is written.
+pub fn plan(result: &ScanResult, config: &JinConfig, options: &RenameOptions) -> Result<RenamePlan, String> {
+    let (from, to) = (options.from.trim(), options.to.trim());
+    if from.is_empty() || to.is_empty() {
+        return Err("both the old and the new key name are required".to_string());
+    }
+    if from == to {
+        return Err(format!("'{}' already has that name", from));
+    }
+    for file in &options.files {
+        if !result.files.iter().any(|f| f.path == *file) {
+            return Err(format!("{} is not a scanned file", file));
+        }
+    }
+
+    // Cross-format matching needs normalized keys even where jin.toml turns comparison off
+    let keys = KeysConfig {
+        normalize: true,
+        ..config.keys.clone()
+    };
+    let canonical = canonical_key(from, &keys);
+    let secrets = SecretDetector::new(&config.secrets)?;
+
+    let mut plan = RenamePlan::default();
+    for file in &result.files {
+        if !options.files.is_empty() && !options.files.contains(&file.path) {
+            continue;
+        }
+        let entries: Vec<&NormalizedEntry> = result.entries.iter().filter(|e| e.source_file == file.path).collect();
+        let nested = file.format != SourceFormat::Env;
+
+        // The key as spelled, or a table holding it; otherwise its other spellings
+        let exact = entries
+            .iter()
+            .any(|e| e.key == from || (nested && is_below(&e.key, from)));
+        let mut renamed: Vec<RenamedKey> = Vec::new();
+        if exact {
+            renamed.push(RenamedKey {
+                from: from.to_string(),
+                to: to.to_string(),
+            });
+        } else if options.cross_format {
+            for entry in &entries {
+                if canonical_key(&entry.key, &keys) == canonical && !renamed.iter().any(|r| r.from == entry.key) {
+                    renamed.push(RenamedKey {
+                        from: entry.key.clone(),
+                        to: respell(&entry.key, to, &keys),
+                    });
+                }
+            }
+        }
+        if renamed.is_empty() {
+            continue;
+        }
+
+        let edits: Vec<KeyEdit> = renamed
+            .iter()
+            .map(|r| KeyEdit::Rename {
+                key: r.from.clone(),
+                to: r.to.clone(),
+            })
+            .collect();
+        match edit::plan(&result.root, &file.path, &file.format, &edits) {
+            Ok(mut edit) => {
+                edit.diff = masked_diff(&edit, &file.format, &secrets);
+                plan.changes.push(RenameChange { renamed, edit });
+            }
+            Err(error) => plan.conflicts.push(error),
+        }
+    }
+    Ok(plan)
+}
+
+/// Writes planned changes under `root`. Fails before writing anything if a file changed since it was planned.
+pub fn apply(root: &str, changes: &[RenameChange]) -> Result<(), String> {
+    let edits: Vec<FileEdit> = changes.iter().map(|change| change.edit.clone()).collect();
+    edit::apply(root, &edits)
+}
+
+// Renamed lines carry their values, so secrets are masked on both sides before diffing. A renamed
+// key can stop looking secret (`DB_PASSWORD` to `DB_PASS`), so its value stays masked by value.
+fn masked_diff(edit: &FileEdit, format: &SourceFormat, secrets: &SecretDetector) -> String {
+    let before = parse_content(&edit.original, &edit.file, format, secrets).entries;
+    let after = parse_content(&edit.updated, &edit.file, format, secrets).entries;
+    let values: HashSet<&str> = before
+        .iter()
+        .filter(|e| e.is_secret())
+        .filter_map(|e| e.value.as_ref()?.as_str())
+        .collect();
+    unified_diff(
+        &edit.file,
+        &mask_entries(&edit.original, &before, &values, format),
+        &mask_entries(&edit.updated, &after, &values, format),
+    )
+}
+
+// Replaces each secret value where it is defined, and nowhere else, so text that merely contains
+// it (`PORT=8080` next to `PIN=8`) stays as it is
+fn mask_entries(content: &str, entries: &[NormalizedEntry], values: &HashSet<&str>, format: &SourceFormat) -> String {
+    let mut lines: Vec<String> = content.split_inclusive('\n').map(String::from).collect();
+    let mut key_lines: Vec<usize> = entries.iter().filter_map(|e| Some(e.span?.line.saturating_sub(1))).collect();
+    key_lines.sort_unstable();
+
+    for entry in entries {
+        let Some(value) = entry.value.as_ref().and_then(|v| v.as_str()) else {
+            continue;
+        };
+        if value.is_empty() || !(entry.is_secret() || values.contains(value)) {
+            continue;
+        }
+        let Some(span) = entry.span else {
+            continue;
+        };
+        let first = span.line.saturating_sub(1);
+        let Some(line) = lines.get_mut(first) else {
+            continue;
+        };
+
+        // On the key's own line, only the value's first line is masked
+        let from = value_start(line, span.column, &entry.key);
+        let head = value.lines().next().unwrap_or_default().trim();
+        let found = match line[from..].find(head) {
+            Some(offset) if !head.is_empty() => {
+                line.replace_range(from + offset..from + offset + head.len(), MASKED_VALUE);
+                true
+            }
+            _ => false,
+        };
+
+        // The rest of the value takes whole lines, up to the next key at the latest
+        let next_key = key_lines.iter().copied().find(|&l| l > first).unwrap_or(lines.len());
+        let last = match format {
+            // Block and multi-line scalars are indented below their key, however they fold
+            SourceFormat::Yaml => {
+                let indent = indentation(&lines[first]);
+                (first + 1..next_key)
+                    .take_while(|&l| lines[l].trim().is_empty() || indentation(&lines[l]) > indent)
+                    .filter(|&l| !lines[l].trim().is_empty())
+                    .last()
+                    .unwrap_or(first)
+            }
+            // Quoted values keep their line breaks; a value opening on the line after its key
+            // (TOML's `"""`) takes one more line
+            _ => {
+                let below = value.lines().count().saturating_sub(usize::from(found));
+                (first + below).min(next_key.saturating_sub(1)).max(first)
+            }
+        };
+        if last == first && !found {
+            // Written differently than it reads, e.g. with escapes: mask the rest of the line
+            mask_rest(&mut lines[first], from);
+        }
+        for line in &mut lines[first + 1..=last] {
+            mask_rest(line, 0);
+        }
+    }
+    lines.concat()
+}
+
+// Byte offset just past the separator that follows the key, so text in the key is never masked
+fn value_start(line: &str, column: usize, key: &str) -> usize {
+    let start = line.char_indices().nth(column.saturating_sub(1)).map_or(0, |(offset, _)| offset);
+    // The key as written is the last segment of a nested key
+    let name = key.rsplit('.').next().unwrap_or(key);
+    let after_key = line[start..].find(name).map_or(start, |offset| start + offset + name.len());
+    line[after_key..].find(['=', ':']).map_or(after_key, |offset| after_key + offset + 1)
+}
+
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+// Everything from `from` on, apart from surrounding blanks and the line ending
+fn mask_rest(line: &mut String, from: usize) {
+    let end = line.trim_end().len();
+    let Some(rest) = line.get(from..end) else {
+        return;
+    };
+    let start = end - rest.trim_start().len();
+    if start < end {
+        line.replace_range(start..end, MASKED_VALUE);
+    }
+}
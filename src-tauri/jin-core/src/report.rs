@@ -0,0 +1,319 @@
+//! Static scan reports for people without Jin: a self-contained HTML page and Markdown for PR
+//! comments. Both list the summary, the issues and the config matrix, with secret values masked.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::analyzer::{display_value, MASKED_VALUE};
+use crate::check::Severity;
+use crate::types::{Issue, ScanResult};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    Html,
+    Markdown,
+}
+
+pub fn render(result: &ScanResult, format: ReportFormat) -> String {
+    match format {
+        ReportFormat::Html => to_html(result),
+        ReportFormat::Markdown => to_markdown(result),
+    }
+}
+
+/// Keys by file: one row per canonical key, one column per file with entries.
+struct Matrix<'a> {
+    files: Vec<&'a str>,
+    /// Key as first spelled, then each file's display value; `None` where the file lacks the key
+    rows: Vec<(&'a str, Vec<Option<String>>)>,
+}
+
+impl<'a> Matrix<'a> {
+    fn new(result: &'a ScanResult) -> Self {
+        let mut files: Vec<&str> = result
+            .files
+            .iter()
+            .filter(|f| f.count > 0)
+            .map(|f| f.path.as_str())
+            .collect();
+        files.sort();
+
+        let mut by_key: BTreeMap<&str, (&str, Vec<Option<String>>)> = BTreeMap::new();
+        for entry in &result.entries {
+            let Some(column) = files.iter().position(|f| *f == entry.source_file) else {
+                continue;
+            };
+            let (_, cells) = by_key
+                .entry(entry.canonical_key.as_str())
+                .or_insert_with(|| (entry.key.as_str(), vec![None; files.len()]));
+            let value = display_value(entry).unwrap_or_else(|| "null".to_string());
+            // An empty secret has nothing to hide, and "empty" says more than the mask
+            cells[column] = Some(if entry.shows_secret() && !value.is_empty() {
+                MASKED_VALUE.to_string()
+            } else {
+                value
+            });
+        }
+
+        Self {
+            files,
+            rows: by_key.into_values().collect(),
+        }
+    }
+}
+
+// Errors first, keeping the scan's order within a severity
+fn sorted_issues(result: &ScanResult) -> Vec<&Issue> {
+    let mut issues: Vec<&Issue> = result.diagnostics.iter().collect();
+    issues.sort_by_key(|issue| std::cmp::Reverse(issue.severity));
+    issues
+}
+
+fn count(result: &ScanResult, severity: Severity) -> usize {
+    result.diagnostics.iter().filter(|i| i.severity == severity).count()
+}
+
+fn location(issue: &Issue) -> String {
+    match issue.span {
+        Some(span) => format!("{}:{}", issue.file, span.line),
+        None => issue.file.clone(),
+    }
+}
+
+fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Off => "off",
+        Severity::Warning => "warning",
+        Severity::Error => "error",
+    }
+}
+
+/// Markdown report, sized for a pull request comment: the matrix is folded away.
+pub fn to_markdown(result: &ScanResult) -> String {
+    let mut out = String::new();
+    let errors = count(result, Severity::Error);
+    let warnings = count(result, Severity::Warning);
+
+    out.push_str("## Jin scan report\n\n");
+    out.push_str(&format!(
+        "**{}** files, **{}** keys ({} unique) · {} {} · {} {}\n",
+        result.summary.total_files,
+        result.summary.total_keys,
+        result.summary.unique_keys,
+        errors,
+        if errors == 1 { "error" } else { "errors" },
+        warnings,
+        if warnings == 1 { "warning" } else { "warnings" },
+    ));
+    if result.summary.suppressed > 0 || result.summary.baselined > 0 {
+        out.push_str(&format!(
+            "\n{} suppressed, {} in the baseline\n",
+            result.summary.suppressed, result.summary.baselined
+        ));
+    }
+
+    out.push_str("\n### Issues\n\n");
+    if result.diagnostics.is_empty() {
+        out.push_str("No issues found.\n");
+    } else {
+        out.push_str("| Severity | Code | Location | Issue |\n|---|---|---|---|\n");
+        for issue in sorted_issues(result) {
+            out.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                severity_name(issue.severity),
+                issue.code,
+                md_code(&location(issue)),
+                md_text(&issue.message)
+            ));
+        }
+    }
+
+    let matrix = Matrix::new(result);
+    if !matrix.rows.is_empty() {
+        out.push_str(&format!(
+            "\n<details>\n<summary>Config matrix ({} keys, {} files)</summary>\n\n",
+            matrix.rows.len(),
+            matrix.files.len()
+        ));
+        out.push_str("| Key |");
+        for file in &matrix.files {
+            out.push_str(&format!(" {} |", md_code(file)));
+        }
+        out.push_str("\n|---|");
+        out.push_str(&"---|".repeat(matrix.files.len()));
+        out.push('\n');
+        for (key, cells) in &matrix.rows {
+            out.push_str(&format!("| {} |", md_code(key)));
+            for cell in cells {
+                match cell {
+                    Some(value) if value.is_empty() => out.push_str(" *empty* |"),
+                    Some(value) => out.push_str(&format!(" {} |", md_code(value))),
+                    None => out.push_str(" — |"),
+                }
+            }
+            out.push('\n');
+        }
+        out.push_str("\n</details>\n");
+    }
+
+    out
+}
+
+// Text inside a table cell: no line breaks, pipes or raw HTML
+fn md_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace(['\r', '\n'], " ")
+}
+
+// A code span inside a table cell, fenced with enough backticks for its content
+fn md_code(text: &str) -> String {
+    let text = text.replace('|', "\\|").replace(['\r', '\n'], " ");
+    let mut longest = 0;
+    let mut run = 0;
+    for c in text.chars() {
+        run = if c == '`' { run + 1 } else { 0 };
+        longest = longest.max(run);
+    }
+    let fence = "`".repeat(longest + 1);
+    if longest > 0 {
+        format!("{} {} {}", fence, text, fence)
+    } else {
+        format!("{}{}{}", fence, text, fence)
+    }
+}
+
+const HTML_STYLE: &str = r#"
+:root { color-scheme: light dark; --fg: #1f2328; --muted: #656d76; --bg: #ffffff; --panel: #f6f8fa; --line: #d0d7de; --error: #cf222e; --warning: #9a6700; }
+@media (prefers-color-scheme: dark) { :root { --fg: #e6edf3; --muted: #8d96a0; --bg: #0d1117; --panel: #161b22; --line: #30363d; --error: #f85149; --warning: #d29922; } }
+body { margin: 0 auto; max-width: 1200px; padding: 2rem; font: 14px/1.5 -apple-system, BlinkMacSystemFont, "Segoe UI", sans-serif; color: var(--fg); background: var(--bg); }
+h1 { margin: 0; font-size: 1.6rem; }
+h2 { margin-top: 2rem; font-size: 1.2rem; }
+.root { color: var(--muted); font-family: ui-monospace, monospace; }
+.cards { display: flex; flex-wrap: wrap; gap: 0.75rem; margin-top: 1rem; }
+.card { padding: 0.75rem 1rem; border: 1px solid var(--line); border-radius: 8px; background: var(--panel); min-width: 7rem; }
+.card b { display: block; font-size: 1.4rem; }
+.card.error b { color: var(--error); }
+.card.warning b { color: var(--warning); }
+.scroll { overflow-x: auto; }
+table { border-collapse: collapse; width: 100%; }
+th, td { padding: 0.35rem 0.6rem; border: 1px solid var(--line); text-align: left; vertical-align: top; }
+th { background: var(--panel); }
+code, td.value { font-family: ui-monospace, monospace; font-size: 12px; word-break: break-all; }
+.severity { font-weight: 600; text-transform: uppercase; font-size: 11px; }
+.severity.error { color: var(--error); }
+.severity.warning { color: var(--warning); }
+.fix, .missing, .empty { color: var(--muted); }
+"#;
+
+/// Self-contained HTML report: inline styles, no scripts or external resources.
+pub fn to_html(result: &ScanResult) -> String {
+    let mut out = String::new();
+    let errors = count(result, Severity::Error);
+    let warnings = count(result, Severity::Warning);
+
+    out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+    out.push_str(&format!("<title>Jin report: {}</title>\n", html(&result.root)));
+    out.push_str(&format!("<style>{}</style>\n</head>\n<body>\n", HTML_STYLE));
+
+    out.push_str("<h1>Jin scan report</h1>\n");
+    out.push_str(&format!("<div class=\"root\">{}</div>\n", html(&result.root)));
+    out.push_str("<div class=\"cards\">\n");
+    for (label, value, class) in [
+        ("Files", result.summary.total_files, ""),
+        ("Keys", result.summary.total_keys, ""),
+        ("Unique keys", result.summary.unique_keys, ""),
+        ("Errors", errors, " error"),
+        ("Warnings", warnings, " warning"),
+        ("Suppressed", result.summary.suppressed, ""),
+        ("Baselined", result.summary.baselined, ""),
+    ] {
+        out.push_str(&format!(
+            "<div class=\"card{}\"><b>{}</b>{}</div>\n",
+            class, value, label
+        ));
+    }
+    out.push_str("</div>\n");
+
+    out.push_str("<h2>Issues</h2>\n");
+    if result.diagnostics.is_empty() {
+        out.push_str("<p>No issues found.</p>\n");
+    } else {
+        out.push_str("<div class=\"scroll\"><table>\n<tr><th>Severity</th><th>Code</th><th>Location</th><th>Issue</th></tr>\n");
+        for issue in sorted_issues(result) {
+            let severity = severity_name(issue.severity);
+            let fix = issue
+                .fix
+                .as_ref()
+                .map(|fix| format!("<div class=\"fix\">{}</div>", html(fix)))
+                .unwrap_or_default();
+            out.push_str(&format!(
+                "<tr><td class=\"severity {}\">{}</td><td title=\"{}\">{}</td><td><code>{}</code></td><td>{}{}</td></tr>\n",
+                severity,
+                severity,
+                html(issue.category.title()),
+                issue.code,
+                html(&location(issue)),
+                html(&issue.message),
+                fix
+            ));
+        }
+        out.push_str("</table></div>\n");
+    }
+
+    if !result.environments.is_empty() {
+        out.push_str("<h2>Environments</h2>\n<ul>\n");
+        for environment in &result.environments {
+            let files: Vec<String> = environment
+                .files
+                .iter()
+                .map(|f| format!("<code>{}</code>", html(f)))
+                .collect();
+            out.push_str(&format!(
+                "<li><b>{}</b>: {}</li>\n",
+                html(&environment.name),
+                files.join(", ")
+            ));
+        }
+        out.push_str("</ul>\n");
+    }
+
+    let matrix = Matrix::new(result);
+    out.push_str("<h2>Config matrix</h2>\n");
+    if matrix.rows.is_empty() {
+        out.push_str("<p>No keys found.</p>\n");
+    } else {
+        out.push_str("<div class=\"scroll\"><table>\n<tr><th>Key</th>");
+        for file in &matrix.files {
+            out.push_str(&format!("<th><code>{}</code></th>", html(file)));
+        }
+        out.push_str("</tr>\n");
+        for (key, cells) in &matrix.rows {
+            out.push_str(&format!("<tr><th><code>{}</code></th>", html(key)));
+            for cell in cells {
+                match cell {
+                    Some(value) if value.is_empty() => out.push_str("<td class=\"empty\">empty</td>"),
+                    Some(value) => out.push_str(&format!("<td class=\"value\">{}</td>", html(value))),
+                    None => out.push_str("<td class=\"missing\">—</td>"),
+                }
+            }
+            out.push_str("</tr>\n");
+        }
+        out.push_str("</table></div>\n");
+    }
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
@@ -0,0 +1,140 @@
+//! SARIF 2.1.0 output, for code scanning tools that annotate pull requests.
+//!
+//! Every issue category is a rule, and every entry of the scan's issue list is a result. File
+//! locations are relative to the scanned directory, which is declared as the `PROJECTROOT` base.
+
+use serde_json::{json, Value};
+use std::path::Path;
+
+use crate::check::{CheckPolicy, IssueCategory, Severity};
+use crate::types::{Issue, ScanResult};
+
+pub const SARIF_VERSION: &str = "2.1.0";
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const ROOT_BASE_ID: &str = "PROJECTROOT";
+
+/// Builds a SARIF log with one run from a scan result; `rules` sets each rule's default level.
+pub fn to_sarif(result: &ScanResult, rules: &CheckPolicy) -> Value {
+    let rule_descriptors: Vec<Value> = IssueCategory::ALL
+        .iter()
+        .map(|&category| {
+            json!({
+                "id": category.code(),
+                "name": rule_name(category),
+                "shortDescription": { "text": category.title() },
+                "fullDescription": { "text": category.description() },
+                "defaultConfiguration": { "level": level(rules.severity(category)) },
+            })
+        })
+        .collect();
+
+    let results: Vec<Value> = result.diagnostics.iter().map(sarif_result).collect();
+
+    let mut run = json!({
+        "tool": {
+            "driver": {
+                "name": "Jin",
+                "informationUri": "https://github.com/lance0/jin",
+                "version": env!("CARGO_PKG_VERSION"),
+                "rules": rule_descriptors,
+            }
+        },
+        "results": results,
+    });
+    // Lets consumers resolve the relative locations when the scan root isn't the repository root
+    if let Ok(root) = Path::new(&result.root).canonicalize() {
+        run["originalUriBaseIds"] = json!({
+            ROOT_BASE_ID: { "uri": directory_uri(&root) }
+        });
+    }
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": SARIF_VERSION,
+        "runs": [run],
+    })
+}
+
+fn sarif_result(issue: &Issue) -> Value {
+    let mut physical = json!({
+        "artifactLocation": {
+            "uri": encode_path(&issue.file),
+            "uriBaseId": ROOT_BASE_ID,
+        }
+    });
+    if let Some(span) = issue.span {
+        physical["region"] = json!({ "startLine": span.line, "startColumn": span.column });
+    }
+
+    let rule_index = IssueCategory::ALL
+        .iter()
+        .position(|&category| category == issue.category)
+        .unwrap_or_default();
+    let mut result = json!({
+        "ruleId": issue.code,
+        "ruleIndex": rule_index,
+        "level": level(issue.severity),
+        "message": { "text": issue.message },
+        "locations": [{ "physicalLocation": physical }],
+        // Same identity as baseline entries: moving lines around doesn't make an issue new
+        "partialFingerprints": {
+            "jin/v1": format!("{}:{}:{}", issue.code, issue.file, issue.key.as_deref().unwrap_or_default()),
+        },
+    });
+    if let Some(fix) = &issue.fix {
+        result["properties"] = json!({ "fix": fix });
+    }
+    result
+}
+
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Off => "none",
+        Severity::Warning => "warning",
+        Severity::Error => "error",
+    }
+}
+
+// PascalCase form of the title, e.g. "ValueConflict"
+fn rule_name(category: IssueCategory) -> String {
+    category
+        .title()
+        .split(' ')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+fn directory_uri(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let path = path.trim_start_matches("//?/");
+    let mut uri = if path.starts_with('/') {
+        format!("file://{}", encode_path(path))
+    } else {
+        // Windows drive paths
+        format!("file:///{}", encode_path(path))
+    };
+    if !uri.ends_with('/') {
+        uri.push('/');
+    }
+    uri
+}
+
+// Percent-encodes everything but unreserved characters, path separators and drive colons
+fn encode_path(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.replace('\\', "/").bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
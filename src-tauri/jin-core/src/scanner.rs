@@ -0,0 +1,247 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use globset::{Glob, GlobBuilder, GlobMatcher, GlobSet, GlobSetBuilder};
+use ignore::{DirEntry, WalkBuilder};
+
+use crate::types::{DiscoveredFile, FormatMapping, FormatRule, ScanOptions, SourceFormat};
+
+const IGNORE_DIRS: &[&str] = &[
+    "node_modules",
+    ".git",
+    "dist",
+    "build",
+    "target",
+    "venv",
+    "__pycache__",
+    ".next",
+    ".nuxt",
+    "coverage",
+    ".cache",
+];
+
+// Lockfiles and Jin's own settings share config extensions but are never app configuration
+const DEFAULT_FORMAT_RULES: &[(&str, FormatMapping)] = &[
+    (crate::config::CONFIG_FILE, FormatMapping::Ignore),
+    ("package-lock.json", FormatMapping::Ignore),
+    ("npm-shrinkwrap.json", FormatMapping::Ignore),
+    ("pnpm-lock.yaml", FormatMapping::Ignore),
+];
+
+// Project-level ignore file, same syntax as .gitignore (globs, `!` negation)
+pub const JIN_IGNORE_FILE: &str = ".jinignore";
+
+pub fn scan_directory(root_path: &str, options: &ScanOptions) -> Result<Vec<DiscoveredFile>, String> {
+    let root = Path::new(root_path);
+
+    if !root.exists() {
+        return Err(format!("Path does not exist: {}", root_path));
+    }
+
+    if !root.is_dir() {
+        return Err(format!("Path is not a directory: {}", root_path));
+    }
+
+    let classifier = FileClassifier::new(options)?;
+    let ignore_dirs: Arc<Vec<String>> = Arc::new(match &options.ignore_dirs {
+        Some(dirs) => dirs.clone(),
+        None => IGNORE_DIRS.iter().map(|d| d.to_string()).collect(),
+    });
+    let mut discovered: HashMap<PathBuf, SourceFormat> = HashMap::new();
+    let mut visited_dirs: HashSet<PathBuf> = HashSet::new();
+
+    // Pass 1: honor .gitignore, .git/info/exclude, global git excludes and .jinignore
+    for entry in build_walker(root, true, &ignore_dirs).build() {
+        match entry {
+            Ok(entry) => {
+                let Some(file_type) = entry.file_type() else {
+                    continue;
+                };
+                if file_type.is_dir() {
+                    visited_dirs.insert(entry.path().to_path_buf());
+                } else if file_type.is_file() {
+                    let relative = relative_path(root, &entry);
+                    if let Some(format) = classifier.classify(&relative) {
+                        discovered.insert(relative, format);
+                    }
+                }
+            }
+            Err(e) => {
+                eprintln!("Warning: Failed to read entry: {}", e);
+            }
+        }
+    }
+
+    // Pass 2: .env files are usually gitignored on purpose, so collect them from every
+    // directory pass 1 entered, applying only .jinignore to the files themselves
+    let visited_dirs = Arc::new(visited_dirs);
+    let mut walker = build_walker(root, false, &ignore_dirs);
+    walker.filter_entry(move |e| {
+        !is_ignored(e, &ignore_dirs)
+            && (!e.file_type().map(|t| t.is_dir()).unwrap_or(false) || visited_dirs.contains(e.path()))
+    });
+
+    for entry in walker.build().flatten() {
+        if entry.file_type().map(|t| t.is_file()).unwrap_or(false) {
+            let relative = relative_path(root, &entry);
+            if let Some(SourceFormat::Env) = classifier.classify(&relative) {
+                discovered.insert(relative, SourceFormat::Env);
+            }
+        }
+    }
+
+    // Convert to DiscoveredFile with placeholder counts
+    let files: Vec<DiscoveredFile> = discovered
+        .into_iter()
+        .map(|(path, format)| DiscoveredFile {
+            path: path.to_string_lossy().to_string(),
+            format,
+            count: 0, // Will be filled in by parser
+        })
+        .collect();
+
+    Ok(files)
+}
+
+/// Lists every file under `root` that git and .jinignore don't exclude, relative to `root`.
+pub(crate) fn walk_files(root: &Path, options: &ScanOptions) -> Vec<PathBuf> {
+    let ignore_dirs: Arc<Vec<String>> = Arc::new(match &options.ignore_dirs {
+        Some(dirs) => dirs.clone(),
+        None => IGNORE_DIRS.iter().map(|d| d.to_string()).collect(),
+    });
+    build_walker(root, true, &ignore_dirs)
+        .build()
+        .flatten()
+        .filter(|entry| entry.file_type().map(|t| t.is_file()).unwrap_or(false))
+        .map(|entry| relative_path(root, &entry))
+        .collect()
+}
+
+fn build_walker(root: &Path, respect_git: bool, ignore_dirs: &Arc<Vec<String>>) -> WalkBuilder {
+    let ignore_dirs = Arc::clone(ignore_dirs);
+    let mut builder = WalkBuilder::new(root);
+    builder
+        .follow_links(false)
+        .hidden(false) // .env files are dotfiles
+        .ignore(false)
+        .git_ignore(respect_git)
+        .git_exclude(respect_git)
+        .git_global(respect_git)
+        .require_git(false)
+        .add_custom_ignore_filename(JIN_IGNORE_FILE)
+        .filter_entry(move |e| !is_ignored(e, &ignore_dirs));
+    builder
+}
+
+fn relative_path(root: &Path, entry: &DirEntry) -> PathBuf {
+    entry
+        .path()
+        .strip_prefix(root)
+        .unwrap_or(entry.path())
+        .to_path_buf()
+}
+
+fn is_ignored(entry: &DirEntry, ignore_dirs: &[String]) -> bool {
+    entry
+        .file_name()
+        .to_str()
+        .map(|s| ignore_dirs.iter().any(|d| d == s))
+        .unwrap_or(false)
+}
+
+/// Decides whether a file is scanned and in which format, from include/exclude globs
+/// and mapping rules.
+struct FileClassifier {
+    include: Option<GlobSet>,
+    exclude: GlobSet,
+    rules: Vec<(GlobMatcher, FormatMapping)>,
+}
+
+impl FileClassifier {
+    fn new(options: &ScanOptions) -> Result<Self, String> {
+        let include = if options.include.is_empty() {
+            None
+        } else {
+            Some(build_glob_set(&options.include)?)
+        };
+        let exclude = build_glob_set(&options.exclude)?;
+
+        let default_rules = DEFAULT_FORMAT_RULES.iter().map(|(pattern, format)| FormatRule {
+            pattern: pattern.to_string(),
+            format: *format,
+        });
+        let rules = options
+            .format_rules
+            .iter()
+            .cloned()
+            .chain(default_rules)
+            .map(|rule| Ok((build_glob(&rule.pattern)?.compile_matcher(), rule.format)))
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(Self { include, exclude, rules })
+    }
+
+    fn classify(&self, relative_path: &Path) -> Option<SourceFormat> {
+        if self.exclude.is_match(relative_path) {
+            return None;
+        }
+        if let Some(include) = &self.include {
+            if !include.is_match(relative_path) {
+                return None;
+            }
+        }
+
+        match self.rules.iter().find(|(matcher, _)| matcher.is_match(relative_path)) {
+            Some((_, FormatMapping::Env)) => Some(SourceFormat::Env),
+            Some((_, FormatMapping::Yaml)) => Some(SourceFormat::Yaml),
+            Some((_, FormatMapping::Json)) => Some(SourceFormat::Json),
+            Some((_, FormatMapping::Toml)) => Some(SourceFormat::Toml),
+            Some((_, FormatMapping::Ignore)) => None,
+            None => detect_format(relative_path),
+        }
+    }
+}
+
+fn build_glob(pattern: &str) -> Result<Glob, String> {
+    let pattern = pattern.trim_start_matches("./");
+    // Like .gitignore, a pattern without a slash matches at any depth
+    let pattern = if pattern.contains('/') {
+        pattern.trim_start_matches('/').to_string()
+    } else {
+        format!("**/{}", pattern)
+    };
+
+    GlobBuilder::new(&pattern)
+        .literal_separator(true)
+        .build()
+        .map_err(|e| format!("Invalid glob '{}': {}", pattern, e))
+}
+
+pub(crate) fn build_glob_set(patterns: &[String]) -> Result<GlobSet, String> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(build_glob(pattern)?);
+    }
+    builder.build().map_err(|e| format!("Invalid glob set: {}", e))
+}
+
+fn detect_format(path: &Path) -> Option<SourceFormat> {
+    let file_name = path.file_name()?.to_str()?;
+
+    // Check for .env files
+    if file_name.starts_with(".env") {
+        return Some(SourceFormat::Env);
+    }
+
+    // Check by extension
+    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+        match ext.to_lowercase().as_str() {
+            "yaml" | "yml" => return Some(SourceFormat::Yaml),
+            "json" => return Some(SourceFormat::Json),
+            "toml" => return Some(SourceFormat::Toml),
+            _ => {}
+        }
+    }
+
+    None
+}
@@ -0,0 +1,292 @@
+//! JSON Schema validation for YAML, JSON and TOML files, run on the parsed document before it is
+//! flattened into entries.
+//!
+//! A file's schema is, in order of preference:
+//! 1. named by the file itself: a top-level `$schema` key, a `# yaml-language-server: $schema=...`
+//!    modeline, or a `#:schema ...` comment in TOML;
+//! 2. mapped in jin.toml under `[[schemas.mappings]]`;
+//! 3. found in the local schema store: through its SchemaStore-style `catalog.json`, or as
+//!    `<file stem>.schema.json`.
+//!
+//! Schemas are never downloaded. A remote `$schema` URL is looked up in the store by its file name,
+//! and files whose schema isn't available locally are skipped.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+use globset::GlobSet;
+use jsonschema::paths::LocationSegment;
+use jsonschema::Validator;
+
+use crate::parser::Document;
+use crate::scanner::build_glob_set;
+use crate::types::{SchemaViolation, SourceFormat, Span};
+
+/// Catalog file looked for in the schema store.
+pub const CATALOG_FILE: &str = "catalog.json";
+
+/// `[schemas]` in jin.toml.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SchemasConfig {
+    /// Directory of local schemas, relative to the project root
+    pub store: Option<String>,
+    /// Glob -> schema, checked in order
+    pub mappings: Vec<SchemaMapping>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SchemaMapping {
+    pub pattern: String,
+    /// Schema path relative to the project root
+    pub schema: String,
+}
+
+impl SchemasConfig {
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        for mapping in &self.mappings {
+            build_glob_set(std::slice::from_ref(&mapping.pattern))
+                .map_err(|e| format!("schemas.mappings: {}", e))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Catalog {
+    #[serde(default)]
+    schemas: Vec<CatalogEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CatalogEntry {
+    #[serde(default, rename = "fileMatch")]
+    file_match: Vec<String>,
+    url: String,
+}
+
+/// Resolves and compiles the schemas for a project; each schema is compiled once.
+pub struct SchemaRegistry {
+    root: PathBuf,
+    store: Option<PathBuf>,
+    mappings: Vec<(GlobSet, PathBuf)>,
+    catalog: Vec<(GlobSet, String)>,
+    validators: HashMap<PathBuf, Result<Validator, String>>,
+}
+
+impl SchemaRegistry {
+    pub fn new(root: &Path, config: &SchemasConfig) -> Result<Self, String> {
+        let mappings = config
+            .mappings
+            .iter()
+            .map(|mapping| {
+                let globs = build_glob_set(std::slice::from_ref(&mapping.pattern))
+                    .map_err(|e| format!("schemas.mappings: {}", e))?;
+                Ok((globs, root.join(&mapping.schema)))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let store = config.store.as_ref().map(|dir| root.join(dir));
+        let catalog = match &store {
+            Some(dir) => load_catalog(&dir.join(CATALOG_FILE))?,
+            None => Vec::new(),
+        };
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            store,
+            mappings,
+            catalog,
+            validators: HashMap::new(),
+        })
+    }
+
+    /// Validates in-memory `content` as if it were read from `file`.
+    pub fn validate(&mut self, file: &str, format: &SourceFormat, content: &str) -> Vec<SchemaViolation> {
+        match Document::parse(content, format) {
+            Some(document) => self.validate_document(file, &document),
+            None => Vec::new(),
+        }
+    }
+
+    /// Validates the document parsed from `file` (see `ParsedFile::document`).
+    pub fn validate_document(&mut self, file: &str, document: &Document) -> Vec<SchemaViolation> {
+        let Some(schema_path) = self.schema_for(file, document) else {
+            return Vec::new();
+        };
+        let schema = self.display_path(&schema_path);
+
+        // `$schema` names the schema; it isn't part of the configuration
+        let mut value = document.value.clone();
+        if let Some(object) = value.as_object_mut() {
+            object.remove("$schema");
+        }
+
+        let validator = match self
+            .validators
+            .entry(schema_path.clone())
+            .or_insert_with(|| compile(&schema_path))
+        {
+            Ok(validator) => validator,
+            Err(message) => {
+                return vec![SchemaViolation {
+                    file: file.to_string(),
+                    path: String::new(),
+                    message: format!("Could not load schema: {}", message),
+                    schema,
+                    span: None,
+                }];
+            }
+        };
+
+        validator
+            .iter_errors(&value)
+            .map(|error| {
+                let segments: Vec<String> = error
+                    .instance_path()
+                    .iter()
+                    .map(|segment| match segment {
+                        LocationSegment::Property(name) => name.to_string(),
+                        LocationSegment::Index(index) => index.to_string(),
+                    })
+                    .collect();
+                SchemaViolation {
+                    file: file.to_string(),
+                    path: segments.join("."),
+                    message: error.to_string(),
+                    schema: schema.clone(),
+                    span: nearest_span(&document.spans, &segments),
+                }
+            })
+            .collect()
+    }
+
+    fn schema_for(&self, file: &str, document: &Document) -> Option<PathBuf> {
+        let declared = document
+            .value
+            .get("$schema")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .or_else(|| document.modeline.clone());
+        if let Some(reference) = declared {
+            let dir = self.root.join(file);
+            let dir = dir.parent().unwrap_or(&self.root);
+            return self.resolve_reference(&reference, dir);
+        }
+
+        let path = Path::new(file);
+        if let Some((_, schema)) = self.mappings.iter().find(|(globs, _)| globs.is_match(path)) {
+            return Some(schema.clone());
+        }
+        let store = self.store.as_ref()?;
+        if let Some((_, url)) = self.catalog.iter().find(|(globs, _)| globs.is_match(path)) {
+            return self.resolve_reference(url, store);
+        }
+        let stem = path.file_stem()?.to_str()?;
+        Some(store.join(format!("{}.schema.json", stem))).filter(|p| p.is_file())
+    }
+
+    // Local paths are relative to `dir`; URLs are looked up in the store by file name
+    fn resolve_reference(&self, reference: &str, dir: &Path) -> Option<PathBuf> {
+        if let Some(path) = reference.strip_prefix("file://") {
+            return Some(PathBuf::from(path));
+        }
+        if reference.contains("://") {
+            let name = reference.trim_end_matches('/').rsplit('/').next()?;
+            let local = self.store.as_ref()?.join(name);
+            return local.is_file().then_some(local);
+        }
+        Some(dir.join(reference))
+    }
+
+    // Relative to the root when possible, for stable output
+    fn display_path(&self, path: &Path) -> String {
+        let path = normalize(path);
+        path.strip_prefix(normalize(&self.root))
+            .unwrap_or(&path)
+            .to_string_lossy()
+            .to_string()
+    }
+}
+
+fn load_catalog(path: &Path) -> Result<Vec<(GlobSet, String)>, String> {
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let catalog: Catalog = serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid schema catalog {}: {}", path.display(), e))?;
+
+    catalog
+        .schemas
+        .into_iter()
+        .filter(|entry| !entry.file_match.is_empty())
+        .map(|entry| Ok((build_glob_set(&entry.file_match)?, entry.url)))
+        .collect()
+}
+
+fn compile(path: &Path) -> Result<Validator, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let schema: serde_json::Value =
+        serde_json::from_str(&content).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    // Relative `$ref`s resolve against the schema's own location
+    let mut options = jsonschema::options();
+    if let Ok(absolute) = path.canonicalize() {
+        options = options.with_base_uri(file_uri(&absolute));
+    }
+    options
+        .build(&schema)
+        .map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn file_uri(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    if path.starts_with('/') {
+        format!("file://{}", path)
+    } else {
+        format!("file:///{}", path)
+    }
+}
+
+// `# yaml-language-server: $schema=...` (YAML) or `#:schema ...` (TOML), in the leading comments
+pub(crate) fn modeline_schema(content: &str) -> Option<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .take_while(|line| line.is_empty() || line.starts_with('#'))
+        .find_map(|line| {
+            let comment = line.trim_start_matches('#').trim();
+            comment
+                .strip_prefix("yaml-language-server:")
+                .and_then(|rest| rest.trim().strip_prefix("$schema="))
+                .or_else(|| line.strip_prefix("#:schema"))
+                .map(|schema| schema.trim().to_string())
+        })
+        .filter(|schema| !schema.is_empty())
+}
+
+// The violation's own key, or the closest enclosing key that has a position
+fn nearest_span(spans: &HashMap<String, Span>, segments: &[String]) -> Option<Span> {
+    (1..=segments.len())
+        .rev()
+        .find_map(|len| spans.get(&segments[..len].join(".")).copied())
+}
+
+// Drops `.` and `..` without touching the filesystem
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
@@ -0,0 +1,201 @@
+//! Write-back for missing keys: appends the keys an env file lacks, leaving every existing line
+//! as it was. Changes are planned first, so they can be previewed as a diff before being applied.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::analyzer::MASKED_VALUE;
+use crate::config::JinConfig;
+use crate::dotenv;
+use crate::edit::{self, write_checked};
+use crate::environments::EnvironmentMap;
+use crate::textdiff::unified_diff;
+use crate::types::{NormalizedEntry, ScanResult, SourceFormat};
+
+/// How missing keys are filled in.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncOptions {
+    /// Env files to update, relative to the scan root; all files with missing keys when empty
+    pub files: Vec<String>,
+    /// Value written for keys without a usable template value
+    pub placeholder: String,
+    /// Copy values from the template a file is compared to; secrets always get the placeholder
+    pub template_values: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncedKey {
+    pub key: String,
+    pub value: String,
+    /// Whether `value` was copied from the template
+    pub from_template: bool,
+}
+
+/// The planned change to one env file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncEdit {
+    pub file: String,
+    pub added: Vec<SyncedKey>,
+    /// File content when the edit was planned; `apply` refuses to write if it has changed since
+    pub original: String,
+    pub updated: String,
+    /// Unified diff from `original` to `updated`, with secret values in the context masked
+    pub diff: String,
+}
+
+/// Plans the edits that add each file's missing keys, in the order the keys appear where they
+/// were expected from. Nothing is written.
+pub fn plan(result: &ScanResult, config: &JinConfig, options: &SyncOptions) -> Result<Vec<SyncEdit>, String> {
+    for file in &options.files {
+        let scanned = result
+            .files
+            .iter()
+            .any(|f| f.path == *file && f.format == SourceFormat::Env);
+        if !scanned {
+            return Err(format!("{} is not a scanned env file", file));
+        }
+    }
+
+    let file_kinds: Vec<(String, bool)> = result
+        .files
+        .iter()
+        .map(|f| (f.path.clone(), f.format == SourceFormat::Env))
+        .collect();
+    let map = EnvironmentMap::build(&file_kinds, &config.environments);
+
+    let mut edits = Vec::new();
+    for missing in &result.issues.missing_by_env_file {
+        if !options.files.is_empty() && !options.files.contains(&missing.file) {
+            continue;
+        }
+        // Values are only worth copying from a template; peers hold another environment's settings
+        let template = match missing.compared_to.as_slice() {
+            [only] if map.is_template(only) => Some(only.as_str()),
+            _ => None,
+        };
+
+        // Entries the keys were expected from; if one can't be found, keep the reported order
+        let mut sources: Vec<&NormalizedEntry> = missing
+            .missing_keys
+            .iter()
+            .map(|key| {
+                result
+                    .entries
+                    .iter()
+                    .find(|e| e.key == *key && missing.compared_to.contains(&e.source_file))
+            })
+            .collect::<Option<Vec<_>>>()
+            .unwrap_or_default();
+        sources.sort_by_key(|e| {
+            let file_index = missing.compared_to.iter().position(|f| *f == e.source_file);
+            (file_index, e.span.map(|s| (s.line, s.column)))
+        });
+
+        // Each key with whether its value is template text that expands `$`
+        let added: Vec<(SyncedKey, bool)> = if sources.is_empty() {
+            missing
+                .missing_keys
+                .iter()
+                .map(|key| {
+                    let key = SyncedKey {
+                        key: key.clone(),
+                        value: options.placeholder.clone(),
+                        from_template: false,
+                    };
+                    (key, false)
+                })
+                .collect()
+        } else {
+            sources
+                .iter()
+                .map(|entry| {
+                    let copy = options.template_values
+                        && template == Some(entry.source_file.as_str())
+                        && !entry.is_secret();
+                    let template_value = match &entry.value {
+                        Some(serde_json::Value::String(s)) if copy && !s.is_empty() => Some(s.clone()),
+                        _ => None,
+                    };
+                    let expands = template_value.is_some()
+                        && (!entry.references.is_empty() || entry.resolved_value.is_some());
+                    let key = SyncedKey {
+                        key: entry.key.clone(),
+                        from_template: template_value.is_some(),
+                        value: template_value.unwrap_or_else(|| options.placeholder.clone()),
+                    };
+                    (key, expands)
+                })
+                .collect()
+        };
+        // Copied `${VAR}` references and `$$` escapes stay live, so the value means what it meant in
+        // the template; everything else is written literally
+        let written: Vec<String> = added
+            .iter()
+            .map(|(key, expands)| {
+                if *expands {
+                    Ok(dotenv::double_quoted(&key.value))
+                } else {
+                    dotenv::format_value(&key.value).map_err(|e| format!("'{}' in {}: {}", key.key, missing.file, e))
+                }
+            })
+            .collect::<Result<_, String>>()?;
+        let added: Vec<SyncedKey> = added.into_iter().map(|(key, _)| key).collect();
+
+        let path = Path::new(&result.root).join(&missing.file);
+        let original = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", missing.file, e))?;
+        let assignments: Vec<(&str, &str)> =
+            added.iter().zip(&written).map(|(k, value)| (k.key.as_str(), value.as_str())).collect();
+        let updated = edit::dotenv::append(&original, &assignments);
+        let secrets: HashSet<&str> = result
+            .entries
+            .iter()
+            .filter(|e| e.source_file == missing.file && e.is_secret())
+            .map(|e| e.key.as_str())
+            .collect();
+        let diff = mask_context(&unified_diff(&missing.file, &original, &updated), &secrets);
+
+        edits.push(SyncEdit {
+            file: missing.file.clone(),
+            added,
+            original,
+            updated,
+            diff,
+        });
+    }
+    Ok(edits)
+}
+
+/// Writes planned edits under `root`. Fails before writing anything if a file changed since it was planned.
+pub fn apply(root: &str, edits: &[SyncEdit]) -> Result<(), String> {
+    write_checked(
+        root,
+        edits
+            .iter()
+            .map(|edit| (edit.file.as_str(), edit.original.as_str(), edit.updated.as_str())),
+    )
+}
+
+// Sync only adds lines, so only the context can show existing secrets
+fn mask_context(diff: &str, secrets: &HashSet<&str>) -> String {
+    if secrets.is_empty() {
+        return diff.to_string();
+    }
+    let mut masked = String::with_capacity(diff.len());
+    for line in diff.lines() {
+        let secret = line.strip_prefix(' ').and_then(|content| {
+            let entry = dotenv::parse(content).entries.into_iter().next()?;
+            secrets.contains(entry.key.as_str()).then_some(entry.key)
+        });
+        match secret {
+            Some(key) => masked.push_str(&format!(" {}={}", key, MASKED_VALUE)),
+            None => masked.push_str(line),
+        }
+        masked.push('\n');
+    }
+    masked
+}
@@ -0,0 +1,134 @@
+//! Unified diffs of file contents, for previewing write-back changes before they are applied.
+
+const CONTEXT_LINES: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Same,
+    Removed,
+    Added,
+}
+
+/// Renders the change from `old` to `new` as a unified diff of `path`; empty when they are equal.
+pub fn unified_diff(path: &str, old: &str, new: &str) -> String {
+    if old == new {
+        return String::new();
+    }
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let ops = diff_lines(&old_lines, &new_lines);
+
+    let mut out = format!("--- a/{}\n+++ b/{}\n", path, path);
+    // Line position of each op in the old and new file
+    let mut positions = Vec::with_capacity(ops.len());
+    let (mut old_line, mut new_line) = (0, 0);
+    for op in &ops {
+        positions.push((old_line, new_line));
+        match op {
+            Op::Same => {
+                old_line += 1;
+                new_line += 1;
+            }
+            Op::Removed => old_line += 1,
+            Op::Added => new_line += 1,
+        }
+    }
+
+    let mut index = 0;
+    while index < ops.len() {
+        let Some(first_change) = (index..ops.len()).find(|&i| ops[i] != Op::Same) else {
+            break;
+        };
+        let start = first_change.saturating_sub(CONTEXT_LINES).max(index);
+        // Extend the hunk while the next change is close enough to share context
+        let mut end = first_change;
+        let mut unchanged = 0;
+        let mut cursor = first_change;
+        while cursor < ops.len() {
+            if ops[cursor] == Op::Same {
+                unchanged += 1;
+                if unchanged > CONTEXT_LINES * 2 {
+                    break;
+                }
+            } else {
+                unchanged = 0;
+                end = cursor;
+            }
+            cursor += 1;
+        }
+        let stop = (end + 1 + CONTEXT_LINES).min(ops.len());
+
+        let (old_start, new_start) = positions[start];
+        let old_count = ops[start..stop].iter().filter(|op| **op != Op::Added).count();
+        let new_count = ops[start..stop].iter().filter(|op| **op != Op::Removed).count();
+        out.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            hunk_range(old_start, old_count),
+            hunk_range(new_start, new_count)
+        ));
+        for i in start..stop {
+            let (old_line, new_line) = positions[i];
+            match ops[i] {
+                Op::Same => out.push_str(&format!(" {}\n", old_lines[old_line])),
+                Op::Removed => out.push_str(&format!("-{}\n", old_lines[old_line])),
+                Op::Added => out.push_str(&format!("+{}\n", new_lines[new_line])),
+            }
+        }
+        index = stop;
+    }
+    out
+}
+
+fn hunk_range(start: usize, count: usize) -> String {
+    // An empty range names the line before it
+    let first = if count == 0 { start } else { start + 1 };
+    if count == 1 {
+        first.to_string()
+    } else {
+        format!("{},{}", first, count)
+    }
+}
+
+// Longest common subsequence over the lines that differ, after trimming the shared ends. On ties,
+// removals come before additions, as in other diff tools
+fn diff_lines(old: &[&str], new: &[&str]) -> Vec<Op> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    let (n, m) = (old_mid.len(), new_mid.len());
+    let mut lengths = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[i][j] = if old_mid[i] == new_mid[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = vec![Op::Same; prefix];
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && old_mid[i] == new_mid[j] {
+            ops.push(Op::Same);
+            i += 1;
+            j += 1;
+        } else if j < m && (i == n || lengths[i][j + 1] > lengths[i + 1][j]) {
+            ops.push(Op::Added);
+            j += 1;
+        } else {
+            ops.push(Op::Removed);
+            i += 1;
+        }
+    }
+    ops.extend(std::iter::repeat_n(Op::Same, suffix));
+    ops
+}
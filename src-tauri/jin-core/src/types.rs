@@ -0,0 +1,446 @@
+use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
+
+use crate::check::{IssueCategory, Severity};
+use crate::diagnostics::Suppression;
+use crate::environments::Environment;
+use crate::parser::Document;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceFormat {
+    Env,
+    Yaml,
+    Json,
+    Toml,
+}
+
+/// Target of a filename mapping rule: parse as a format, or skip the file entirely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FormatMapping {
+    Env,
+    Yaml,
+    Json,
+    Toml,
+    Ignore,
+}
+
+impl std::str::FromStr for FormatMapping {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "env" => Ok(FormatMapping::Env),
+            "yaml" | "yml" => Ok(FormatMapping::Yaml),
+            "json" => Ok(FormatMapping::Json),
+            "toml" => Ok(FormatMapping::Toml),
+            "ignore" => Ok(FormatMapping::Ignore),
+            other => Err(format!(
+                "Unknown format '{}' (expected env, yaml, json, toml or ignore)",
+                other
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FormatRule {
+    pub pattern: String,
+    pub format: FormatMapping,
+}
+
+impl std::str::FromStr for FormatRule {
+    type Err = String;
+
+    /// Parses `PATTERN=FORMAT`, e.g. `config/*.conf=env`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (pattern, format) = s
+            .rsplit_once('=')
+            .ok_or_else(|| format!("Invalid mapping '{}' (expected PATTERN=FORMAT)", s))?;
+        Ok(FormatRule {
+            pattern: pattern.trim().to_string(),
+            format: format.parse()?,
+        })
+    }
+}
+
+/// Which files a scan considers and how they are read. Globs are relative to the scan root;
+/// patterns without a `/` match at any depth, like .gitignore entries.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScanOptions {
+    /// Directory names never descended into; `None` uses the built-in list
+    pub ignore_dirs: Option<Vec<String>>,
+    /// When non-empty, only files matching one of these globs are scanned
+    pub include: Vec<String>,
+    /// Files matching any of these globs are never scanned
+    pub exclude: Vec<String>,
+    /// First matching rule decides the format, before extension-based detection
+    pub format_rules: Vec<FormatRule>,
+    /// Maximum number of files read and parsed at once
+    pub concurrency: usize,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self {
+            ignore_dirs: None,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            format_rules: Vec::new(),
+            concurrency: 16,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Env,
+    Json,
+    Yaml,
+}
+
+impl ExportFormat {
+    /// Template file name used when no output path is given.
+    pub fn default_file_name(self) -> &'static str {
+        match self {
+            ExportFormat::Env => ".env.example",
+            ExportFormat::Json => "config.example.json",
+            ExportFormat::Yaml => "config.example.yaml",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InferredType {
+    String,
+    Number,
+    Boolean,
+    Null,
+    Unknown,
+}
+
+/// 1-based position in a source file; columns count characters, not bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NormalizedEntry {
+    /// The key as spelled in `source_file`
+    pub key: String,
+    /// The key as compared across files (see `keys::canonical_key`)
+    #[serde(default)]
+    pub canonical_key: String,
+    pub value: Option<serde_json::Value>,
+    pub source_file: String,
+    pub source_format: SourceFormat,
+    pub inferred_type: InferredType,
+    /// 0 to 1; see `secrets::SECRET_THRESHOLD`
+    #[serde(default)]
+    pub secret_confidence: f64,
+    /// The pattern or heuristic behind `secret_confidence`
+    pub secret_rule: Option<String>,
+    /// Where the key is written in `source_file`
+    pub span: Option<Span>,
+    /// Variables referenced by an env value, e.g. `DB_HOST` in `${DB_HOST}:5432`
+    #[serde(default)]
+    pub references: Vec<String>,
+    /// `value` with references and `$` escapes expanded; `None` when it has neither
+    pub resolved_value: Option<serde_json::Value>,
+    /// Whether `resolved_value` includes the value of a secret it references
+    #[serde(default)]
+    pub references_secret: bool,
+}
+
+impl NormalizedEntry {
+    pub fn is_secret(&self) -> bool {
+        self.secret_confidence >= crate::secrets::SECRET_THRESHOLD
+    }
+
+    /// Whether the value as displayed (see `analyzer::display_value`) would show a secret: the
+    /// entry is one, or its resolved value was built from one.
+    pub fn shows_secret(&self) -> bool {
+        self.is_secret() || self.references_secret
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscoveredFile {
+    pub path: String,
+    pub format: SourceFormat,
+    pub count: usize,
+}
+
+/// How the values of a duplicated key compare across files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DuplicateKind {
+    SameValue,
+    DifferentValues,
+    DifferentTypes,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateValue {
+    pub file: String,
+    /// The key as spelled in this file
+    pub key: String,
+    /// Display value; secrets are masked
+    pub value: Option<String>,
+    pub inferred_type: InferredType,
+    pub is_secret: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Duplicate {
+    /// The key as spelled in the first file
+    pub key: String,
+    pub canonical_key: String,
+    pub files: Vec<String>,
+    pub kind: DuplicateKind,
+    /// One value per file, in the same order as `files`
+    pub values: Vec<DuplicateValue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MissingKeys {
+    pub file: String,
+    pub missing_keys: Vec<String>,
+    /// Environment the file belongs to, if any
+    pub environment: Option<String>,
+    /// The template, or the peer files, the keys were expected from
+    pub compared_to: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParseError {
+    pub file: String,
+    pub message: String,
+    pub span: Option<Span>,
+}
+
+/// A `${VAR}` reference that no file in the value's load order defines, and that has no default.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UndefinedReference {
+    pub file: String,
+    pub key: String,
+    pub reference: String,
+    pub span: Option<Span>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReferenceCycle {
+    pub file: String,
+    /// Keys on the cycle, in the order they reference each other
+    pub keys: Vec<String>,
+}
+
+/// An entry that fails a `[[policies]]` rule from jin.toml.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyViolation {
+    /// The policy's `id`
+    pub policy: String,
+    pub severity: Severity,
+    pub file: String,
+    pub key: String,
+    pub message: String,
+    pub span: Option<Span>,
+}
+
+/// A place in source code that reads an environment variable.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodeReference {
+    pub key: String,
+    pub file: String,
+    pub span: Span,
+}
+
+/// A key in an env file that no indexed source file reads.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnusedKey {
+    pub key: String,
+    pub file: String,
+    pub span: Option<Span>,
+}
+
+/// A variable read by source code that no env file defines; `file` is the source file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UndefinedKey {
+    pub key: String,
+    pub file: String,
+    pub span: Option<Span>,
+}
+
+/// A YAML, JSON or TOML document that doesn't satisfy its JSON Schema.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaViolation {
+    pub file: String,
+    /// Dotted path to the offending value; empty for the document itself
+    pub path: String,
+    pub message: String,
+    /// The schema the file was checked against
+    pub schema: String,
+    pub span: Option<Span>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExposureKind {
+    /// The file is in the git index
+    Tracked,
+    /// The file is untracked and no ignore rule covers it
+    NotIgnored,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExposedSecret {
+    pub key: String,
+    pub rule: Option<String>,
+    pub span: Option<Span>,
+}
+
+/// A file with real secret values that is committed, or would be by `git add`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecretExposure {
+    pub file: String,
+    pub kind: ExposureKind,
+    pub secrets: Vec<ExposedSecret>,
+}
+
+/// One issue in the flat list every check reports into, with a stable code such as `JIN002`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Issue {
+    pub code: String,
+    pub category: IssueCategory,
+    pub severity: Severity,
+    pub message: String,
+    pub file: String,
+    pub key: Option<String>,
+    pub span: Option<Span>,
+    /// A suggested way to resolve the issue
+    pub fix: Option<String>,
+}
+
+impl Issue {
+    pub fn new(category: IssueCategory, severity: Severity, message: String, file: &str) -> Self {
+        Self {
+            code: category.code().to_string(),
+            category,
+            severity,
+            message,
+            file: file.to_string(),
+            key: None,
+            span: None,
+            fix: None,
+        }
+    }
+
+    pub fn with_key(mut self, key: &str) -> Self {
+        self.key = Some(key.to_string());
+        self
+    }
+
+    pub fn with_span(mut self, span: Option<Span>) -> Self {
+        self.span = span;
+        self
+    }
+
+    pub fn with_fix(mut self, fix: String) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanIssues {
+    pub duplicates: Vec<Duplicate>,
+    pub missing_by_env_file: Vec<MissingKeys>,
+    pub parse_errors: Vec<ParseError>,
+    pub undefined_references: Vec<UndefinedReference>,
+    pub reference_cycles: Vec<ReferenceCycle>,
+    #[serde(default)]
+    pub exposed_secrets: Vec<SecretExposure>,
+    #[serde(default)]
+    pub schema_violations: Vec<SchemaViolation>,
+    #[serde(default)]
+    pub policy_violations: Vec<PolicyViolation>,
+    #[serde(default)]
+    pub unused_keys: Vec<UnusedKey>,
+    #[serde(default)]
+    pub undefined_keys: Vec<UndefinedKey>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanSummary {
+    pub total_files: usize,
+    pub total_keys: usize,
+    pub unique_keys: usize,
+    /// Issues silenced by `jin-ignore` comments or `[[allow]]` entries
+    #[serde(default)]
+    pub suppressed: usize,
+    /// Known issues left out because the baseline records them
+    #[serde(default)]
+    pub baselined: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanResult {
+    pub root: String,
+    pub files: Vec<DiscoveredFile>,
+    pub environments: Vec<Environment>,
+    pub entries: Vec<NormalizedEntry>,
+    pub issues: ScanIssues,
+    /// Every issue in `issues` that isn't turned off, as one list
+    #[serde(default)]
+    pub diagnostics: Vec<Issue>,
+    pub summary: ScanSummary,
+}
+
+// Cache structures for incremental scanning
+#[derive(Debug, Clone)]
+pub struct FileCache {
+    pub modified_time: SystemTime,
+    pub format: SourceFormat,
+    /// `SecretDetector::fingerprint` of the settings the entries were scored with
+    pub secrets: u64,
+    pub entries: Vec<NormalizedEntry>,
+    pub errors: Vec<ParseError>,
+    pub suppressions: Vec<Suppression>,
+    pub document: Option<Document>,
+}
+
+#[derive(Clone, Default)]
+pub struct ScanCacheState {
+    // Key: file path, Value: cached file data
+    // DashMap is already thread-safe, we just need to make the state cloneable via Arc
+    cache: std::sync::Arc<dashmap::DashMap<String, FileCache>>,
+}
+
+impl ScanCacheState {
+    pub fn new() -> Self {
+        Self {
+            cache: std::sync::Arc::new(dashmap::DashMap::new()),
+        }
+    }
+
+    pub fn get(&self, path: &str) -> Option<FileCache> {
+        self.cache.get(path).map(|entry| entry.value().clone())
+    }
+
+    pub fn insert(&self, path: String, cache_entry: FileCache) {
+        self.cache.insert(path, cache_entry);
+    }
+}
@@ -0,0 +1,296 @@
+//! Source code indexing: finds the environment variables code reads, so the scanner can report
+//! keys no code uses and variables code reads that no env file defines.
+//!
+//! Recognized reads:
+//! - JavaScript/TypeScript: `process.env.KEY`, `process.env["KEY"]`, `import.meta.env.KEY` and
+//!   destructuring such as `const { KEY } = process.env`
+//! - Rust: `env!("KEY")`, `option_env!("KEY")`, `env::var("KEY")`, `env::var_os("KEY")`
+//! - Python: `os.environ["KEY"]`, `os.environ.get("KEY")`, `os.getenv("KEY")`
+//! - Go: `os.Getenv("KEY")`, `os.LookupEnv("KEY")`
+//!
+//! Reads with computed names are skipped, since the key isn't known without running the code.
+
+use globset::{Glob, GlobMatcher, GlobSet};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::OnceLock;
+
+use crate::diagnostics::{self, Suppression};
+use crate::keys::{canonical_key, KeysConfig};
+use crate::locate::span_at;
+use crate::scanner::{build_glob_set, walk_files};
+use crate::types::{CodeReference, NormalizedEntry, ScanOptions, SourceFormat, UndefinedKey, UnusedKey};
+
+// Larger files are almost always bundles or generated code
+const MAX_SOURCE_SIZE: u64 = 1024 * 1024;
+
+// Variables set by the OS, CI, Cargo or the bundler rather than by env files
+const DEFAULT_IGNORE_KEYS: &[&str] = &[
+    "NODE_ENV", "CI", "HOME", "PATH", "PWD", "USER", "SHELL", "LANG", "TZ", "TMPDIR", "CARGO_*",
+    "OUT_DIR", "MODE", "BASE_URL", "DEV", "PROD", "SSR",
+];
+
+/// `[code]` in jin.toml: which source files are indexed, if any.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CodeConfig {
+    /// Index source files; off by default
+    pub enabled: bool,
+    /// When non-empty, only source files matching one of these globs are indexed
+    pub include: Vec<String>,
+    /// Source files matching any of these globs are never indexed
+    pub exclude: Vec<String>,
+    /// Key globs never reported as unused or undefined; replaces the built-in list
+    pub ignore_keys: Vec<String>,
+}
+
+impl Default for CodeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            ignore_keys: DEFAULT_IGNORE_KEYS.iter().map(|k| k.to_string()).collect(),
+        }
+    }
+}
+
+impl CodeConfig {
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        build_glob_set(&self.include).map_err(|e| format!("code.include: {}", e))?;
+        build_glob_set(&self.exclude).map_err(|e| format!("code.exclude: {}", e))?;
+        key_matchers(&self.ignore_keys)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    JavaScript,
+    Rust,
+    Python,
+    Go,
+}
+
+impl Language {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" | "mts" | "cts" | "vue" | "svelte" | "astro" => {
+                Some(Language::JavaScript)
+            }
+            "rs" => Some(Language::Rust),
+            "py" => Some(Language::Python),
+            "go" => Some(Language::Go),
+            _ => None,
+        }
+    }
+}
+
+/// Environment variable reads found in a project's source files.
+#[derive(Debug, Clone, Default)]
+pub struct SourceIndex {
+    /// Number of source files read
+    pub files: usize,
+    pub references: Vec<CodeReference>,
+    /// `jin-ignore` comments in source files
+    pub suppressions: HashMap<String, Vec<Suppression>>,
+}
+
+/// Reads every supported source file under `root` that git, .jinignore and `config` allow.
+pub fn index(root: &Path, config: &CodeConfig, scan: &ScanOptions) -> Result<SourceIndex, String> {
+    let include: Option<GlobSet> = if config.include.is_empty() {
+        None
+    } else {
+        Some(build_glob_set(&config.include).map_err(|e| format!("code.include: {}", e))?)
+    };
+    let exclude = build_glob_set(&config.exclude).map_err(|e| format!("code.exclude: {}", e))?;
+
+    let mut index = SourceIndex::default();
+    let mut paths = walk_files(root, scan);
+    paths.sort();
+    for relative in paths {
+        let Some(language) = Language::from_path(&relative) else {
+            continue;
+        };
+        if exclude.is_match(&relative) || include.as_ref().is_some_and(|set| !set.is_match(&relative)) {
+            continue;
+        }
+        let full_path = root.join(&relative);
+        if std::fs::metadata(&full_path).map(|m| m.len() > MAX_SOURCE_SIZE).unwrap_or(true) {
+            continue;
+        }
+        // Binary or non-UTF-8 files can't hold a readable variable name
+        let Ok(content) = std::fs::read_to_string(&full_path) else {
+            continue;
+        };
+
+        let file = relative.to_string_lossy().replace('\\', "/");
+        index.files += 1;
+        index.references.extend(references(&content, &file, language));
+        let suppressions = diagnostics::suppressions(&content);
+        if !suppressions.is_empty() {
+            index.suppressions.insert(file, suppressions);
+        }
+    }
+    Ok(index)
+}
+
+/// Finds the environment variables `content` reads, in source order.
+pub fn references(content: &str, file: &str, language: Language) -> Vec<CodeReference> {
+    let mut found: Vec<(usize, String)> = Vec::new();
+    for pattern in patterns(language) {
+        for captures in pattern.captures_iter(content) {
+            if let Some(key) = captures.get(1) {
+                found.push((key.start(), key.as_str().to_string()));
+            }
+        }
+    }
+
+    if language == Language::JavaScript {
+        for captures in destructuring_pattern().captures_iter(content) {
+            let Some(names) = captures.get(1) else {
+                continue;
+            };
+            let mut offset = names.start();
+            for part in names.as_str().split(',') {
+                // `KEY`, `KEY: alias` or `KEY = fallback`; `...rest` isn't a key
+                let name = part.split([':', '=']).next().unwrap_or_default().trim();
+                if is_identifier(name) {
+                    let start = offset + part.find(name).unwrap_or(0);
+                    found.push((start, name.to_string()));
+                }
+                offset += part.len() + 1;
+            }
+        }
+    }
+
+    found.sort();
+    found
+        .into_iter()
+        .map(|(offset, key)| CodeReference {
+            key,
+            file: file.to_string(),
+            span: span_at(content, offset),
+        })
+        .collect()
+}
+
+/// Keys in env files that no source file reads and no other value references.
+pub fn find_unused(entries: &[NormalizedEntry], index: &SourceIndex, config: &CodeConfig, keys: &KeysConfig) -> Vec<UnusedKey> {
+    // With nothing indexed, every key would look unused
+    if index.files == 0 {
+        return Vec::new();
+    }
+    let ignored = key_matchers(&config.ignore_keys).unwrap_or_default();
+    let mut used: HashSet<String> = index
+        .references
+        .iter()
+        .map(|r| canonical_key(&r.key, keys))
+        .collect();
+    for entry in entries {
+        used.extend(entry.references.iter().map(|key| canonical_key(key, keys)));
+    }
+
+    entries
+        .iter()
+        .filter(|e| e.source_format == SourceFormat::Env)
+        .filter(|e| !used.contains(&e.canonical_key) && !is_ignored(&e.key, &ignored))
+        .map(|e| UnusedKey {
+            key: e.key.clone(),
+            file: e.source_file.clone(),
+            span: e.span,
+        })
+        .collect()
+}
+
+/// Variables source code reads that no env file defines.
+pub fn find_undefined(entries: &[NormalizedEntry], index: &SourceIndex, config: &CodeConfig, keys: &KeysConfig) -> Vec<UndefinedKey> {
+    let ignored = key_matchers(&config.ignore_keys).unwrap_or_default();
+    let defined: HashSet<&str> = entries
+        .iter()
+        .filter(|e| e.source_format == SourceFormat::Env)
+        .map(|e| e.canonical_key.as_str())
+        .collect();
+
+    index
+        .references
+        .iter()
+        .filter(|r| !defined.contains(canonical_key(&r.key, keys).as_str()) && !is_ignored(&r.key, &ignored))
+        .map(|r| UndefinedKey {
+            key: r.key.clone(),
+            file: r.file.clone(),
+            span: Some(r.span),
+        })
+        .collect()
+}
+
+fn patterns(language: Language) -> &'static [Regex] {
+    static JAVASCRIPT: OnceLock<Vec<Regex>> = OnceLock::new();
+    static RUST: OnceLock<Vec<Regex>> = OnceLock::new();
+    static PYTHON: OnceLock<Vec<Regex>> = OnceLock::new();
+    static GO: OnceLock<Vec<Regex>> = OnceLock::new();
+
+    let (cell, sources): (&OnceLock<Vec<Regex>>, &[&str]) = match language {
+        Language::JavaScript => (
+            &JAVASCRIPT,
+            &[
+                r"\b(?:process\.env|import\.meta\.env)\.([A-Za-z_$][\w$]*)",
+                r#"\b(?:process\.env|import\.meta\.env)\[\s*['"`]([^'"`\s]+)['"`]\s*\]"#,
+            ],
+        ),
+        Language::Rust => (
+            &RUST,
+            &[
+                r#"\b(?:option_)?env!\(\s*"([^"\s]+)""#,
+                r#"\benv::var(?:_os)?\(\s*"([^"\s]+)""#,
+            ],
+        ),
+        Language::Python => (
+            &PYTHON,
+            &[
+                r#"\bos\.environ\[\s*['"]([^'"\s]+)['"]\s*\]"#,
+                r#"\bos\.(?:environ\.get|getenv)\(\s*['"]([^'"\s]+)['"]"#,
+            ],
+        ),
+        Language::Go => (&GO, &[r#"\bos\.(?:Getenv|LookupEnv)\(\s*"([^"\s]+)""#]),
+    };
+    cell.get_or_init(|| {
+        sources
+            .iter()
+            .map(|source| Regex::new(source).expect("code reference pattern"))
+            .collect()
+    })
+}
+
+fn destructuring_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r"\{([^{}]*)\}\s*=\s*(?:process\.env|import\.meta\.env)\b").expect("destructuring pattern")
+    })
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+fn key_matchers(patterns: &[String]) -> Result<Vec<GlobMatcher>, String> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            Glob::new(pattern)
+                .map(|glob| glob.compile_matcher())
+                .map_err(|e| format!("code.ignore_keys: invalid glob '{}': {}", pattern, e))
+        })
+        .collect()
+}
+
+fn is_ignored(key: &str, ignored: &[GlobMatcher]) -> bool {
+    ignored.iter().any(|glob| glob.is_match(key))
+}
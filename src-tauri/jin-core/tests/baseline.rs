@@ -0,0 +1,69 @@
+mod common;
+
+use std::collections::HashMap;
+
+use common::Project;
+use jin_core::secrets::SecretDetector;
+use jin_core::diagnostics::{self, IssueList};
+use jin_core::{analyze_entries, parse_content, Baseline, JinConfig, ScanIssues, SourceFormat};
+
+fn scan(files: &[(&str, &str)], baseline: Option<&Baseline>) -> (ScanIssues, IssueList) {
+    let config = JinConfig::default();
+    let entries: Vec<_> = files
+        .iter()
+        .flat_map(|(path, content)| {
+            parse_content(content, path, &SourceFormat::Env, &SecretDetector::default()).entries
+        })
+        .collect();
+
+    let mut issues = analyze_entries(&entries, &config);
+    let list = diagnostics::apply(&mut issues, &entries, &config, &HashMap::new(), baseline);
+    (issues, list)
+}
+
+#[test]
+fn reports_only_issues_missing_from_the_baseline() {
+    let before = [
+        (".env.development", "PORT=80\nNAME=app\n"),
+        (".env.production", "PORT=443\nNAME=app\n"),
+    ];
+    let (_, known) = scan(&before, None);
+    let baseline = Baseline::from_issues(&known.issues);
+    assert_eq!(baseline.issues.len(), 2);
+
+    // Moving lines around keeps known issues known; the new conflict on HOST is reported
+    let after = [
+        (".env.development", "NAME=app\nPORT=8080\nHOST=dev\n"),
+        (".env.production", "HOST=prod\nPORT=443\nNAME=app\n"),
+    ];
+    let (issues, list) = scan(&after, Some(&baseline));
+
+    let new: Vec<(&str, Option<&str>)> = list.issues.iter().map(|i| (i.code.as_str(), i.key.as_deref())).collect();
+    assert_eq!(new, vec![("JIN002", Some("HOST"))]);
+    assert_eq!(list.baselined, 2);
+    assert_eq!(issues.duplicates.len(), 1);
+}
+
+#[test]
+fn round_trips_through_a_file() {
+    let (_, list) = scan(
+        &[
+            (".env.development", "URL=${HOST}\n"),
+            (".env.production", "URL=${HOST}\n"),
+        ],
+        None,
+    );
+    let baseline = Baseline::from_issues(&list.issues);
+
+    let project = Project::new("baseline", &[]);
+    let path = project.join("jin-baseline.json");
+    baseline.save(&path).unwrap();
+    let content = std::fs::read_to_string(&path).unwrap();
+    let loaded = Baseline::load(&path);
+    std::fs::write(&path, r#"{"version": 99, "issues": []}"#).unwrap();
+    let too_new = Baseline::load(&path);
+
+    assert_eq!(loaded.unwrap(), baseline);
+    assert!(content.contains(r#""code": "JIN005""#), "{}", content);
+    assert!(too_new.unwrap_err().contains("version 99"));
+}
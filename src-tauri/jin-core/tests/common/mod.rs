@@ -0,0 +1,62 @@
+//! Helpers shared by the integration tests.
+// Each test file uses a different subset of them
+#![allow(dead_code)]
+
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+use jin_core::{ScanResult, Scanner};
+
+/// A temporary project directory, removed when it goes out of scope, also when a test fails.
+pub struct Project(PathBuf);
+
+impl Project {
+    /// Creates `jin-<name>-<pid>` in the temp directory, holding `files` (path relative to it, content).
+    pub fn new(name: &str, files: &[(&str, &str)]) -> Self {
+        let dir = std::env::temp_dir().join(format!("jin-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let project = Project(dir);
+        for (path, content) in files {
+            project.write(path, content);
+        }
+        project
+    }
+
+    pub fn write(&self, path: &str, content: &str) {
+        let path = self.0.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    pub fn read(&self, path: &str) -> String {
+        fs::read_to_string(self.0.join(path)).unwrap()
+    }
+
+    /// The directory as the string the core APIs take.
+    pub fn root(&self) -> String {
+        self.0.to_string_lossy().to_string()
+    }
+
+    /// Scans the project with its jin.toml, if it has one.
+    pub fn scan(&self) -> (ScanResult, Scanner) {
+        let scanner = Scanner::discover(self.root()).unwrap();
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        (runtime.block_on(scanner.scan()).unwrap(), scanner)
+    }
+}
+
+impl Deref for Project {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for Project {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
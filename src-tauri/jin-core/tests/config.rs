@@ -0,0 +1,23 @@
+mod common;
+
+use common::Project;
+use jin_core::JinConfig;
+
+#[test]
+fn misspelled_sections_and_settings_are_errors() {
+    for toml in [
+        "[rule]\nduplicates = \"off\"\n",
+        "[rules]\nduplicate = \"off\"\n",
+        "[scan]\nexclud = [\"fixtures/**\"]\n",
+        "[secrets]\nkeyword = [\"DSN\"]\n",
+        "[[scan.format_rules]]\npattern = \"*.conf\"\nformats = \"env\"\n",
+        "[[allow]]\ncode = [\"JIN002\"]\n",
+    ] {
+        let project = Project::new("config-unknown", &[("jin.toml", toml)]);
+        let error = JinConfig::discover(&project.root()).unwrap_err();
+        assert!(error.contains("unknown field"), "{}: {}", toml, error);
+    }
+
+    let project = Project::new("config-known", &[("jin.toml", "[rules]\nduplicates = \"off\"\n")]);
+    assert!(JinConfig::discover(&project.root()).is_ok());
+}
@@ -0,0 +1,132 @@
+mod common;
+
+use std::collections::HashMap;
+
+use common::Project;
+use jin_core::secrets::SecretDetector;
+use jin_core::diagnostics::{self, Suppression};
+use jin_core::{analyze_entries, interpolate, parse_content, Issue, JinConfig, ScanIssues, SourceFormat};
+
+fn scan(files: &[(&str, &str)], config: &str) -> (ScanIssues, Vec<Issue>, usize) {
+    let config: JinConfig = toml::from_str(config).unwrap();
+    let mut entries = Vec::new();
+    let mut suppressions: HashMap<String, Vec<Suppression>> = HashMap::new();
+    for (path, content) in files {
+        let parsed = parse_content(content, path, &SourceFormat::Env, &SecretDetector::default());
+        entries.extend(parsed.entries);
+        suppressions.insert(path.to_string(), parsed.suppressions);
+    }
+    interpolate::apply(&mut entries);
+
+    let mut issues = analyze_entries(&entries, &config);
+    let list = diagnostics::apply(&mut issues, &entries, &config, &suppressions, None);
+    (issues, list.issues, list.suppressed)
+}
+
+fn codes(issues: &[Issue]) -> Vec<(&str, Option<&str>)> {
+    issues.iter().map(|i| (i.code.as_str(), i.key.as_deref())).collect()
+}
+
+#[test]
+fn reads_inline_comments() {
+    let found = diagnostics::suppressions(
+        "A=1 # jin-ignore: JIN002, JIN005\n# jin-ignore\n\nB=2\n# jin-ignore-file: JIN003\nC=3 # not jin-ignore-ish\n",
+    );
+
+    assert_eq!(
+        found,
+        vec![
+            Suppression {
+                line: Some(1),
+                codes: vec!["JIN002".to_string(), "JIN005".to_string()],
+            },
+            Suppression {
+                line: Some(4),
+                codes: Vec::new(),
+            },
+            Suppression {
+                line: None,
+                codes: vec!["JIN003".to_string()],
+            },
+        ]
+    );
+}
+
+#[test]
+fn lists_every_issue_with_its_code() {
+    let (_, issues, suppressed) = scan(
+        &[
+            (".env.example", "URL=\n"),
+            (".env.development", "PORT=80\nLINK=${HOST}\nNAME=app\n"),
+            (".env.production", "PORT=443\nNAME=app\n"),
+        ],
+        "",
+    );
+
+    assert_eq!(
+        codes(&issues),
+        vec![
+            ("JIN001", Some("NAME")),
+            ("JIN002", Some("PORT")),
+            ("JIN003", Some("URL")),
+            ("JIN003", Some("URL")),
+            ("JIN005", Some("LINK")),
+        ]
+    );
+    assert_eq!(issues[3].file, ".env.production");
+    assert_eq!(issues[3].fix.as_deref(), Some("Add URL to .env.production"));
+    assert_eq!(issues[4].span.map(|s| s.line), Some(2));
+    assert_eq!(suppressed, 0);
+}
+
+#[test]
+fn silences_issues_marked_inline() {
+    let (issues, list, suppressed) = scan(
+        &[
+            (".env.example", "URL=\n"),
+            (
+                ".env.development",
+                "PORT=80 # jin-ignore: JIN002\n# jin-ignore\n\nLINK=${HOST}\nNAME=app\n# jin-ignore-file: JIN003\n",
+            ),
+            (".env.production", "# jin-ignore-file: jin003\nPORT=443\nNAME=app\n"),
+        ],
+        "",
+    );
+
+    assert_eq!(codes(&list), vec![("JIN001", Some("NAME"))]);
+    assert_eq!(suppressed, 4);
+    assert!(issues.undefined_references.is_empty());
+    assert!(issues.missing_by_env_file.is_empty());
+    assert_eq!(issues.duplicates.len(), 1);
+}
+
+#[test]
+fn silences_issues_allowed_in_config() {
+    let (_, list, suppressed) = scan(
+        &[
+            (".env.development", "PORT=80\nNAME=app\n"),
+            (".env.production", "PORT=443\nNAME=app\n"),
+        ],
+        r#"
+        [rules]
+        duplicates = "off"
+
+        [[allow]]
+        codes = ["JIN002"]
+        keys = ["PORT"]
+        reason = "Ports differ per environment"
+        "#,
+    );
+
+    // Issues whose category is off stay out of the list
+    assert!(list.is_empty());
+    assert_eq!(suppressed, 1);
+}
+
+#[test]
+fn rejects_empty_allow_rules() {
+    let project = Project::new("diagnostics", &[("jin.toml", "[[allow]]\nreason = \"everything\"\n")]);
+    let result = JinConfig::discover(&project.root());
+
+    assert!(result.unwrap_err().contains("allow"));
+}
@@ -0,0 +1,114 @@
+mod common;
+
+use common::Project;
+use jin_core::analyzer::MASKED_VALUE;
+use jin_core::check::{evaluate, CheckPolicy, Severity};
+use jin_core::secrets::SecretDetector;
+use jin_core::interpolate;
+use jin_core::{analyze_entries, parse_content, Duplicate, DuplicateKind, JinConfig, SourceFormat};
+
+fn duplicates(files: &[(&str, SourceFormat, &str)]) -> Vec<Duplicate> {
+    let mut entries: Vec<_> = files
+        .iter()
+        .flat_map(|(path, format, content)| {
+            parse_content(content, path, format, &SecretDetector::default()).entries
+        })
+        .collect();
+    interpolate::apply(&mut entries);
+    analyze_entries(&entries, &JinConfig::default()).duplicates
+}
+
+fn kind_of(duplicates: &[Duplicate], key: &str) -> DuplicateKind {
+    duplicates.iter().find(|d| d.key == key).unwrap().kind
+}
+
+#[test]
+fn classifies_value_conflicts() {
+    let duplicates = duplicates(&[
+        (".env", SourceFormat::Env, "SAME=x\nHOST=a\nPORT=8080\nDEBUG=yes\n"),
+        (".env.production", SourceFormat::Env, "SAME=x\nHOST=b\n"),
+        ("config.yaml", SourceFormat::Yaml, "PORT: 8080\nDEBUG: true\n"),
+    ]);
+
+    assert_eq!(kind_of(&duplicates, "SAME"), DuplicateKind::SameValue);
+    assert_eq!(kind_of(&duplicates, "HOST"), DuplicateKind::DifferentValues);
+    // An env string that looks like the structured file's number is not a type conflict
+    assert_eq!(kind_of(&duplicates, "PORT"), DuplicateKind::SameValue);
+    assert_eq!(kind_of(&duplicates, "DEBUG"), DuplicateKind::DifferentTypes);
+}
+
+#[test]
+fn lists_values_per_file_and_masks_secrets() {
+    let duplicates = duplicates(&[
+        (".env", SourceFormat::Env, "HOST=a\nAPI_TOKEN=one\n"),
+        (".env.production", SourceFormat::Env, "HOST=b\nAPI_TOKEN=two\n"),
+    ]);
+
+    let host = duplicates.iter().find(|d| d.key == "HOST").unwrap();
+    let values: Vec<_> = host.values.iter().map(|v| (v.file.as_str(), v.value.as_deref())).collect();
+    assert_eq!(values, vec![(".env", Some("a")), (".env.production", Some("b"))]);
+
+    let token = duplicates.iter().find(|d| d.key == "API_TOKEN").unwrap();
+    assert_eq!(token.kind, DuplicateKind::DifferentValues);
+    assert!(token.values.iter().all(|v| v.is_secret && v.value.as_deref() == Some(MASKED_VALUE)));
+}
+
+#[test]
+fn compares_resolved_values() {
+    let duplicates = duplicates(&[
+        (".env", SourceFormat::Env, "HOST=db\nURL=postgres://db\n"),
+        ("api/.env", SourceFormat::Env, "HOST=db\nURL=postgres://${HOST}\n"),
+    ]);
+
+    assert_eq!(kind_of(&duplicates, "URL"), DuplicateKind::SameValue);
+}
+
+#[test]
+fn masks_values_built_from_secrets() {
+    let project = Project::new(
+        "duplicates-references",
+        &[
+            (".env", "DB_PASSWORD=hunter2supersecretvalue\nDATABASE_URL=postgres://app:${DB_PASSWORD}@db/app\n"),
+            (".env.production", "DATABASE_URL=postgres://app@prod-db/app\n"),
+        ],
+    );
+    let (result, _) = project.scan();
+
+    let url = result.issues.duplicates.iter().find(|d| d.key == "DATABASE_URL").unwrap();
+    assert_eq!(url.kind, DuplicateKind::DifferentValues);
+    let values: Vec<_> = url.values.iter().map(|v| (v.file.as_str(), v.value.as_deref())).collect();
+    assert_eq!(
+        values,
+        vec![(".env", Some(MASKED_VALUE)), (".env.production", Some("postgres://app@prod-db/app"))]
+    );
+}
+
+#[test]
+fn counts_each_duplicate_under_one_rule() {
+    let project = Project::new(
+        "duplicates-rules",
+        &[(".env.development", "PORT=3000\nNAME=app\n"), (".env.production", "PORT=4000\nNAME=app\n")],
+    );
+    let (result, _) = project.scan();
+
+    // NAME is a duplicate (JIN001), PORT a value conflict (JIN002), never both
+    let outcome = evaluate(&result, &CheckPolicy::default());
+    assert_eq!(outcome.warnings, result.diagnostics.len());
+    assert_eq!(outcome.warnings, 2);
+
+    let policy = CheckPolicy {
+        duplicates: Severity::Error,
+        value_conflicts: Severity::Off,
+        ..CheckPolicy::default()
+    };
+    let outcome = evaluate(&result, &policy);
+    assert_eq!(outcome.errors, 1);
+    assert!(!outcome.passed);
+
+    let policy = CheckPolicy {
+        duplicates: Severity::Off,
+        value_conflicts: Severity::Error,
+        ..CheckPolicy::default()
+    };
+    assert_eq!(evaluate(&result, &policy).errors, 1);
+}
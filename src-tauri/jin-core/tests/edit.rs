@@ -0,0 +1,194 @@
+mod common;
+
+use common::Project;
+use jin_core::edit::{apply, edit_content, plan};
+use jin_core::interpolate::resolve;
+use jin_core::secrets::SecretDetector;
+use jin_core::{parse_content, KeyEdit, SourceFormat};
+use serde_json::json;
+
+fn set(key: &str, value: serde_json::Value) -> KeyEdit {
+    KeyEdit::Set {
+        key: key.to_string(),
+        value,
+    }
+}
+
+fn rename(key: &str, to: &str) -> KeyEdit {
+    KeyEdit::Rename {
+        key: key.to_string(),
+        to: to.to_string(),
+    }
+}
+
+fn delete(key: &str) -> KeyEdit {
+    KeyEdit::Delete { key: key.to_string() }
+}
+
+#[test]
+fn env_edits_keep_quoting_and_comments() {
+    let content = "# db\nexport DB_HOST=localhost # inline\nDB_PASS='se cret'\n\nAPI_URL=\"http://x\"\n";
+    let edited = edit_content(
+        content,
+        &SourceFormat::Env,
+        &[
+            set("DB_HOST", json!("db.internal")),
+            set("DB_PASS", json!("new pass")),
+            rename("API_URL", "API_BASE_URL"),
+            set("PORT", json!(8080)),
+        ],
+    )
+    .unwrap();
+    assert_eq!(
+        edited,
+        "# db\nexport DB_HOST=db.internal # inline\nDB_PASS='new pass'\n\nAPI_BASE_URL=\"http://x\"\nPORT=8080\n"
+    );
+
+    let deleted = edit_content(content, &SourceFormat::Env, &[delete("DB_PASS")]).unwrap();
+    assert_eq!(deleted, "# db\nexport DB_HOST=localhost # inline\n\nAPI_URL=\"http://x\"\n");
+}
+
+#[test]
+fn env_values_with_dollars_read_back_as_set() {
+    let content = "BARE=x\nQUOTED=\"x\"\nSINGLE='x'\n";
+    for value in ["p@$$w0rd", "a$HOME", "${HOST}:5432", "cost $5"] {
+        for key in ["BARE", "QUOTED", "SINGLE", "NEW"] {
+            let edited = edit_content(content, &SourceFormat::Env, &[set(key, json!(value))]).unwrap();
+            let entries = parse_content(&edited, ".env", &SourceFormat::Env, &SecretDetector::default()).entries;
+            let entry = entries.iter().find(|e| e.key == key).unwrap();
+
+            // Nothing is left to expand, so the value reads as it was set
+            assert!(resolve(&entries).values.is_empty(), "{}", edited);
+            assert_eq!(entry.resolved_value, None, "{}", edited);
+            assert_eq!(entry.value, Some(json!(value)), "{}", edited);
+        }
+    }
+
+    let error = edit_content(content, &SourceFormat::Env, &[set("BARE", json!("it's $5"))]).unwrap_err();
+    assert!(error.contains("'BARE'"), "{}", error);
+}
+
+#[test]
+fn structured_edits_keep_layout() {
+    let yaml = "# app\nname: app # the name\ndb:\n  host: 'localhost'\n  port: 5432\nserver:\n  tls: {cert: a.pem}\n";
+    let edited = edit_content(
+        yaml,
+        &SourceFormat::Yaml,
+        &[
+            set("db.host", json!("db.internal")),
+            set("db.user", json!("admin")),
+            set("server.tls.key", json!("key.pem")),
+            rename("name", "title"),
+        ],
+    )
+    .unwrap();
+    assert_eq!(
+        edited,
+        "# app\ntitle: app # the name\ndb:\n  host: 'db.internal'\n  port: 5432\n  user: admin\nserver:\n  tls: {cert: a.pem, key: key.pem}\n"
+    );
+    let moved = edit_content(yaml, &SourceFormat::Yaml, &[rename("db", "services.db")]).unwrap();
+    assert_eq!(
+        moved,
+        "# app\nname: app # the name\nserver:\n  tls: {cert: a.pem}\nservices:\n  db:\n    host: 'localhost'\n    port: 5432\n"
+    );
+
+    let json = "{\n    \"db\": {\n        \"host\": \"localhost\",\n        \"port\": 5432\n    }\n}\n";
+    let edited = edit_content(
+        json,
+        &SourceFormat::Json,
+        &[delete("db.port"), set("cache.url", json!("redis://cache"))],
+    )
+    .unwrap();
+    assert_eq!(
+        edited,
+        "{\n    \"db\": {\n        \"host\": \"localhost\"\n    },\n    \"cache\": {\n        \"url\": \"redis://cache\"\n    }\n}\n"
+    );
+
+    let toml = "# top\ntitle = \"app\"\n\n[db]\nhost = 'localhost'  # the host\nport = 5432\n";
+    let edited = edit_content(
+        toml,
+        &SourceFormat::Toml,
+        &[set("db.host", json!("db.internal")), rename("db.port", "db.db_port"), delete("title")],
+    )
+    .unwrap();
+    assert_eq!(edited, "# top\n\n[db]\nhost = 'db.internal'  # the host\ndb_port = 5432\n");
+}
+
+#[test]
+fn refuses_edits_it_cannot_make_cleanly() {
+    let yaml = "db:\n  host: &host localhost\nreplica: *host\n";
+    let error = edit_content(yaml, &SourceFormat::Yaml, &[set("db.host", json!("db"))]).unwrap_err();
+    assert!(error.contains("without changing other keys"), "{}", error);
+
+    let json = "{\"db\": {\"host\": \"localhost\"}, \"port\": 1}";
+    let cases = [
+        (set("db", json!("x")), "holds nested keys"),
+        (set("port.number", json!(1)), "can't be added below it"),
+        (set("tags", json!(["a"])), "only strings"),
+        (rename("db.host", "port"), "already exists"),
+        (delete("missing"), "not found"),
+    ];
+    for (edit, expected) in cases {
+        let error = edit_content(json, &SourceFormat::Json, &[edit]).unwrap_err();
+        assert!(error.contains(expected), "{}", error);
+    }
+
+    let error = edit_content("a = 1\n", &SourceFormat::Toml, &[set("a", json!(null))]).unwrap_err();
+    assert!(error.contains("no null"), "{}", error);
+}
+
+#[test]
+fn plans_then_applies_unless_the_file_changed() {
+    let project = Project::new("edit-apply", &[(".env", "A=1\nB=2\n")]);
+    let root = project.root();
+
+    let planned = plan(&root, ".env", &SourceFormat::Env, &[set("B", json!("3"))]).unwrap();
+    assert_eq!(planned.diff, "--- a/.env\n+++ b/.env\n@@ -1,2 +1,2 @@\n A=1\n-B=2\n+B=3\n");
+    assert_eq!(project.read(".env"), "A=1\nB=2\n");
+
+    // A file edited after the preview is left alone
+    project.write(".env", "A=1\nB=2\nC=4\n");
+    assert!(apply(&root, &[planned]).is_err());
+    assert_eq!(project.read(".env"), "A=1\nB=2\nC=4\n");
+
+    let planned = plan(&root, ".env", &SourceFormat::Env, &[set("B", json!("3"))]).unwrap();
+    apply(&root, &[planned]).unwrap();
+    assert_eq!(project.read(".env"), "A=1\nB=3\nC=4\n");
+}
+
+#[test]
+fn applies_all_files_or_none() {
+    let project = Project::new("edit-atomic", &[(".env", "A=1\n"), ("config/.env", "B=2\n")]);
+    let root = project.root();
+    let edits = || {
+        vec![
+            plan(&root, ".env", &SourceFormat::Env, &[set("A", json!("10"))]).unwrap(),
+            plan(&root, "config/.env", &SourceFormat::Env, &[set("B", json!("20"))]).unwrap(),
+        ]
+    };
+
+    // Block the second file's temporary copy: the first file must not be replaced either
+    let blocker = format!("config/.env.jin-{}.tmp", std::process::id());
+    std::fs::create_dir(project.join(&blocker)).unwrap();
+    let error = apply(&root, &edits()).unwrap_err();
+    assert!(error.contains("nothing was written"), "{}", error);
+    assert_eq!(project.read(".env"), "A=1\n");
+    assert_eq!(project.read("config/.env"), "B=2\n");
+    assert!(!project.join(format!(".env.jin-{}.tmp", std::process::id())).exists());
+    std::fs::remove_dir(project.join(&blocker)).unwrap();
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(project.join(".env"), std::fs::Permissions::from_mode(0o600)).unwrap();
+    }
+    apply(&root, &edits()).unwrap();
+    assert_eq!(project.read(".env"), "A=10\n");
+    assert_eq!(project.read("config/.env"), "B=20\n");
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(project.join(".env")).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
@@ -0,0 +1,82 @@
+use jin_core::secrets::SecretDetector;
+use jin_core::{analyze_entries, parse_content, JinConfig, MissingKeys, NormalizedEntry, SourceFormat};
+
+fn entries(files: &[(&str, SourceFormat, &str)]) -> Vec<NormalizedEntry> {
+    files
+        .iter()
+        .flat_map(|(path, format, content)| {
+            parse_content(content, path, format, &SecretDetector::default()).entries
+        })
+        .collect()
+}
+
+fn missing(entries: &[NormalizedEntry], config: &JinConfig) -> Vec<MissingKeys> {
+    analyze_entries(entries, config).missing_by_env_file
+}
+
+#[test]
+fn compares_against_the_template() {
+    let entries = entries(&[
+        (".env.example", SourceFormat::Env, "A=\nB=\nC=\n"),
+        (".env", SourceFormat::Env, "A=1\n"),
+        (".env.production", SourceFormat::Env, "B=2\n"),
+        ("config/production.yaml", SourceFormat::Yaml, "C: 3\n"),
+        ("package.json", SourceFormat::Json, r#"{"name": "app"}"#),
+    ]);
+    let missing = missing(&entries, &JinConfig::default());
+
+    // .env.production inherits A from .env and C from config/production.yaml
+    assert_eq!(missing.len(), 1);
+    assert_eq!(missing[0].file, ".env");
+    assert_eq!(missing[0].missing_keys, vec!["B", "C"]);
+    assert_eq!(missing[0].compared_to, vec![".env.example"]);
+}
+
+#[test]
+fn compares_against_peers_without_a_template() {
+    let entries = entries(&[
+        (".env.staging", SourceFormat::Env, "A=1\nB=2\n"),
+        (".env.staging.local", SourceFormat::Env, "C=3\n"),
+        ("config.json", SourceFormat::Json, r#"{"unrelated": true}"#),
+    ]);
+    let missing = missing(&entries, &JinConfig::default());
+
+    assert_eq!(missing.len(), 1);
+    assert_eq!(missing[0].file, ".env.staging");
+    assert_eq!(missing[0].environment.as_deref(), Some("staging"));
+    assert_eq!(missing[0].missing_keys, vec!["C"]);
+}
+
+#[test]
+fn explicit_groups_take_precedence() {
+    let config: JinConfig = toml::from_str(
+        r#"
+        [environments]
+        templates = []
+        infer = false
+
+        [environments.groups]
+        prod = ["deploy/prod.env", "deploy/prod-extra.env"]
+        "#,
+    )
+    .unwrap();
+    let entries = entries(&[
+        ("deploy/prod.env", SourceFormat::Env, "A=1\nB=2\n"),
+        ("deploy/prod-extra.env", SourceFormat::Env, "A=1\n"),
+        ("deploy/dev.env", SourceFormat::Env, "Z=1\n"),
+    ]);
+    let missing = missing(&entries, &config);
+
+    assert_eq!(missing.len(), 1);
+    assert_eq!(missing[0].file, "deploy/prod-extra.env");
+    assert_eq!(missing[0].environment.as_deref(), Some("prod"));
+    assert_eq!(missing[0].missing_keys, vec!["B"]);
+}
+
+#[test]
+fn misspelled_settings_are_errors() {
+    // Neither may become an environment named "template" or "infers"
+    for toml in ["[environments]\ntemplate = [\".env.example\"]\n", "[environments]\ninfers = false\n"] {
+        assert!(toml::from_str::<JinConfig>(toml).is_err(), "{}", toml);
+    }
+}
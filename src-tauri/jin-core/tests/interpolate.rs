@@ -0,0 +1,116 @@
+use jin_core::secrets::SecretDetector;
+use jin_core::interpolate::{self, resolve};
+use jin_core::{parse_content, NormalizedEntry, SourceFormat};
+
+fn env_entries(files: &[(&str, &str)]) -> Vec<NormalizedEntry> {
+    files
+        .iter()
+        .flat_map(|(path, content)| {
+            parse_content(content, path, &SourceFormat::Env, &SecretDetector::default()).entries
+        })
+        .collect()
+}
+
+fn resolved(entries: &[NormalizedEntry], file: &str, key: &str) -> Option<String> {
+    resolve(entries)
+        .values
+        .get(&(file.to_string(), key.to_string()))
+        .cloned()
+}
+
+#[test]
+fn records_references() {
+    let entries = env_entries(&[(".env", "URL=\"${HOST}:$PORT/${DB:-${NAME}}\"\nLIT='${HOST}'\n")]);
+
+    assert_eq!(entries[0].references, vec!["HOST", "PORT", "DB", "NAME"]);
+    assert!(entries[1].references.is_empty());
+}
+
+#[test]
+fn resolves_within_a_file_regardless_of_order() {
+    let entries = env_entries(&[(".env", "URL=http://${HOST}:${PORT}\nHOST=localhost\nPORT=3000\n")]);
+
+    assert_eq!(resolved(&entries, ".env", "URL").as_deref(), Some("http://localhost:3000"));
+    assert!(resolve(&entries).undefined.is_empty());
+}
+
+#[test]
+fn applies_defaults() {
+    let entries = env_entries(&[(".env", "EMPTY=\nA=${PORT:-8080}\nB=${EMPTY:-x}\nC=${EMPTY-x}\n")]);
+
+    assert_eq!(resolved(&entries, ".env", "A").as_deref(), Some("8080"));
+    assert_eq!(resolved(&entries, ".env", "B").as_deref(), Some("x"));
+    assert_eq!(resolved(&entries, ".env", "C").as_deref(), Some(""));
+    assert!(resolve(&entries).undefined.is_empty());
+}
+
+#[test]
+fn escaped_dollars_are_literal() {
+    let entries = env_entries(&[(".env", "A=cost$$5\nB=\"\\${HOME}\"\n")]);
+
+    assert!(entries.iter().all(|e| e.references.is_empty()));
+    assert_eq!(entries[0].resolved_value, Some(serde_json::json!("cost$5")));
+    assert_eq!(entries[1].resolved_value, Some(serde_json::json!("${HOME}")));
+}
+
+#[test]
+fn marks_values_built_from_secrets() {
+    let mut entries = env_entries(&[(
+        ".env",
+        "API_TOKEN=tok_8f2Kq9Lx\nAUTH=Bearer ${API_TOKEN}\nHEADER=${AUTH}\nHOST=db\nURL=${HOST}/${MISSING:-x}\n",
+    )]);
+    interpolate::apply(&mut entries);
+
+    let shown: Vec<_> = entries.iter().filter(|e| e.shows_secret()).map(|e| e.key.as_str()).collect();
+    assert_eq!(shown, vec!["API_TOKEN", "AUTH", "HEADER"]);
+    assert!(!entries[1].is_secret());
+}
+
+#[test]
+fn follows_the_env_load_order() {
+    let entries = env_entries(&[
+        (".env", "HOST=db\nPATH_LIST=/usr/bin\n"),
+        (".env.production", "HOST=prod-db\nPATH_LIST=${PATH_LIST}:/opt\n"),
+        (".env.local", "URL=${HOST}\n"),
+        (".env.production.local", "URL=${HOST}/${PATH_LIST}\n"),
+        ("api/.env", "URL=${HOST}\n"),
+    ]);
+
+    assert_eq!(resolved(&entries, ".env.production", "PATH_LIST").as_deref(), Some("/usr/bin:/opt"));
+    assert_eq!(resolved(&entries, ".env.local", "URL").as_deref(), Some("db"));
+    assert_eq!(
+        resolved(&entries, ".env.production.local", "URL").as_deref(),
+        Some("prod-db//usr/bin:/opt")
+    );
+
+    // Other directories have their own load order
+    let undefined = resolve(&entries).undefined;
+    assert_eq!(undefined.len(), 1);
+    assert_eq!(undefined[0].file, "api/.env");
+    assert_eq!(undefined[0].reference, "HOST");
+}
+
+#[test]
+fn reports_undefined_references() {
+    let entries = env_entries(&[(".env", "\nA=${MISSING}/x\n")]);
+    let resolution = resolve(&entries);
+
+    assert_eq!(resolution.values.get(&(".env".to_string(), "A".to_string())).map(String::as_str), Some("/x"));
+    assert_eq!(resolution.undefined.len(), 1);
+    assert_eq!(resolution.undefined[0].key, "A");
+    assert_eq!(resolution.undefined[0].reference, "MISSING");
+    assert_eq!(resolution.undefined[0].span.map(|s| s.line), Some(2));
+}
+
+#[test]
+fn reports_cycles_once() {
+    let entries = env_entries(&[(".env", "A=${B}\nB=${C}\nC=$A\nD=${A}\nSELF=${SELF}\n")]);
+    let resolution = resolve(&entries);
+
+    assert_eq!(resolution.cycles.len(), 1);
+    assert_eq!(resolution.cycles[0].keys, vec!["A", "B", "C"]);
+    assert!(!resolution.values.contains_key(&(".env".to_string(), "D".to_string())));
+    // A key referring to itself with no earlier definition is undefined, not a cycle
+    assert_eq!(resolution.undefined.len(), 1);
+    assert_eq!(resolution.undefined[0].key, "SELF");
+}
@@ -0,0 +1,65 @@
+use jin_core::secrets::SecretDetector;
+use jin_core::keys::{canonical_key, KeysConfig};
+use jin_core::{analyze_entries, parse_content, JinConfig, SourceFormat};
+
+#[test]
+fn folds_case_and_separators() {
+    let config = KeysConfig::default();
+
+    for key in ["DATABASE_URL", "database.url", "database__url", "databaseUrl", "database-url"] {
+        assert_eq!(canonical_key(key, &config), "database.url", "{}", key);
+    }
+    assert_eq!(canonical_key("redis.maxRetries", &config), "redis.max.retries");
+}
+
+#[test]
+fn strips_configured_prefixes() {
+    let config = KeysConfig {
+        strip_prefixes: vec!["APP_".to_string(), "NEXT_PUBLIC_".to_string()],
+        ..KeysConfig::default()
+    };
+
+    assert_eq!(canonical_key("APP_DATABASE_URL", &config), "database.url");
+    assert_eq!(canonical_key("NEXT_PUBLIC_API_URL", &config), "api.url");
+    assert_eq!(canonical_key("app.port", &config), "port");
+    // Only whole segments are stripped, and a bare prefix stays
+    assert_eq!(canonical_key("APPLICATION_NAME", &config), "application.name");
+    assert_eq!(canonical_key("APP", &config), "app");
+}
+
+#[test]
+fn can_be_disabled() {
+    let config = KeysConfig {
+        normalize: false,
+        ..KeysConfig::default()
+    };
+
+    assert_eq!(canonical_key("DATABASE_URL", &config), "DATABASE_URL");
+}
+
+#[test]
+fn lines_up_keys_across_formats() {
+    let secrets = SecretDetector::default();
+    let mut entries = parse_content("DATABASE_URL=postgres://db\n", ".env", &SourceFormat::Env, &secrets).entries;
+    entries.extend(
+        parse_content("database:\n  url: postgres://db\n", "config.yaml", &SourceFormat::Yaml, &secrets).entries,
+    );
+    let issues = analyze_entries(&entries, &JinConfig::default());
+
+    assert_eq!(issues.duplicates.len(), 1);
+    let duplicate = &issues.duplicates[0];
+    assert_eq!(duplicate.canonical_key, "database.url");
+    let spellings: Vec<_> = duplicate.values.iter().map(|v| v.key.as_str()).collect();
+    assert_eq!(spellings, vec!["DATABASE_URL", "database.url"]);
+}
+
+#[test]
+fn reports_missing_keys_in_the_template_spelling() {
+    let secrets = SecretDetector::default();
+    let mut entries = parse_content("apiUrl=\nDebug=\n", ".env.example", &SourceFormat::Env, &secrets).entries;
+    entries.extend(parse_content("API_URL=x\n", ".env", &SourceFormat::Env, &secrets).entries);
+    let issues = analyze_entries(&entries, &JinConfig::default());
+
+    assert_eq!(issues.missing_by_env_file.len(), 1);
+    assert_eq!(issues.missing_by_env_file[0].missing_keys, vec!["Debug"]);
+}
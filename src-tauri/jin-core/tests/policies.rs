@@ -0,0 +1,125 @@
+mod common;
+
+use common::Project;
+use jin_core::secrets::SecretDetector;
+use jin_core::check::Severity;
+use jin_core::{
+    analyze_entries, interpolate, parse_content, JinConfig, NormalizedEntry, PolicyViolation, SourceFormat,
+};
+
+fn entries(files: &[(&str, SourceFormat, &str)]) -> Vec<NormalizedEntry> {
+    let mut entries: Vec<NormalizedEntry> = files
+        .iter()
+        .flat_map(|(path, format, content)| {
+            parse_content(content, path, format, &SecretDetector::default()).entries
+        })
+        .collect();
+    interpolate::apply(&mut entries);
+    entries
+}
+
+fn violations(policies: &str, entries: &[NormalizedEntry]) -> Vec<PolicyViolation> {
+    let config: JinConfig = toml::from_str(policies).unwrap();
+    analyze_entries(entries, &config).policy_violations
+}
+
+#[test]
+fn checks_types_and_ranges() {
+    let entries = entries(&[
+        (".env", SourceFormat::Env, "PORT=80\nADMIN_PORT=8443\nMETRICS_PORT=abc\n"),
+        ("config.yaml", SourceFormat::Yaml, "server:\n  port: \"9000\"\n"),
+    ]);
+    let found = violations(
+        r#"
+        [[policies]]
+        id = "port"
+        keys = ["*PORT", "*.port"]
+        type = "integer"
+        min = 1024
+        max = 65535
+        "#,
+        &entries,
+    );
+
+    let messages: Vec<&str> = found.iter().map(|v| v.message.as_str()).collect();
+    // A quoted YAML value is a string; env values are parsed
+    assert_eq!(
+        messages,
+        vec![
+            "PORT must be between 1024 and 65535",
+            "METRICS_PORT must be an integer",
+            "server.port must be an integer",
+        ]
+    );
+    assert_eq!(found[0].policy, "port");
+    assert_eq!(found[0].span.map(|s| s.line), Some(1));
+}
+
+#[test]
+fn scopes_policies_to_environments_and_files() {
+    let entries = entries(&[
+        (".env.development", SourceFormat::Env, "DEBUG=true\n"),
+        (".env.production", SourceFormat::Env, "DEBUG=TRUE\n"),
+        ("config/production.yaml", SourceFormat::Yaml, "DEBUG: false\n"),
+    ]);
+    let found = violations(
+        r#"
+        [[policies]]
+        id = "no-debug"
+        keys = ["DEBUG"]
+        environments = ["production"]
+        equals = false
+        severity = "warning"
+        message = "{key}={value} in {file}"
+        "#,
+        &entries,
+    );
+
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].message, "DEBUG=TRUE in .env.production");
+    assert_eq!(found[0].severity, Severity::Warning);
+}
+
+#[test]
+fn checks_formats_and_key_names() {
+    let entries = entries(&[(
+        ".env",
+        SourceFormat::Env,
+        "HOST=db\nDATABASE_URL=postgres://${HOST}:5432/app\nCALLBACK_URL=not a url\napiUrl=http://x\nEMPTY_URL=\n",
+    )]);
+    let found = violations(
+        r#"
+        [[policies]]
+        id = "urls"
+        keys = ["*_URL"]
+        format = "url"
+
+        [[policies]]
+        id = "naming"
+        key_case = "screaming_snake_case"
+        "#,
+        &entries,
+    );
+
+    let found: Vec<(&str, &str)> = found.iter().map(|v| (v.policy.as_str(), v.key.as_str())).collect();
+    assert_eq!(found, vec![("urls", "CALLBACK_URL"), ("naming", "apiUrl")]);
+}
+
+#[test]
+fn rejects_invalid_policies() {
+    let project = Project::new("policies", &[("jin.toml", "[[policies]]\nid = \"bad\"\npattern = \"(\"\n")]);
+    let result = JinConfig::discover(&project.root());
+
+    assert!(result.unwrap_err().contains("policies 'bad'"));
+
+    // Misspelled assertions must not leave a policy that passes everything
+    for (policy, expected) in [
+        ("keys = [\"PORT\"]\nmx = 65535\n", "unknown field"),
+        ("keys = [\"*_URL\"]\nformats = \"url\"\n", "unknown field"),
+        ("keys = [\"*_URL\"]\n", "at least one of"),
+    ] {
+        project.write("jin.toml", &format!("[[policies]]\nid = \"typo\"\n{}", policy));
+        let error = JinConfig::discover(&project.root()).unwrap_err();
+        assert!(error.contains(expected), "{}", error);
+    }
+}
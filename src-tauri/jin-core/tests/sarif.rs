@@ -0,0 +1,53 @@
+mod common;
+
+use common::Project;
+use jin_core::check::CheckPolicy;
+use jin_core::sarif::to_sarif;
+
+#[test]
+fn reports_issues_as_sarif_results() {
+    let project = Project::new(
+        "sarif",
+        &[
+            (".env.example", "HOST=\n"),
+            (".env", "NAME=app\nURL=${MISSING}\n"),
+            ("my config/app.json", "{ broken"),
+        ],
+    );
+    let (result, _) = project.scan();
+    let sarif = to_sarif(&result, &CheckPolicy::default());
+
+    assert_eq!(sarif["version"], "2.1.0");
+    let run = &sarif["runs"][0];
+    let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+    assert_eq!(rules.len(), 11);
+    assert_eq!(rules[2]["id"], "JIN003");
+    assert_eq!(rules[2]["name"], "MissingKey");
+    assert_eq!(rules[2]["defaultConfiguration"]["level"], "error");
+    assert!(run["originalUriBaseIds"]["PROJECTROOT"]["uri"]
+        .as_str()
+        .unwrap()
+        .starts_with("file:///"));
+
+    let results = run["results"].as_array().unwrap();
+    let summary: Vec<(&str, &str)> = results
+        .iter()
+        .map(|r| {
+            (
+                r["ruleId"].as_str().unwrap(),
+                r["locations"][0]["physicalLocation"]["artifactLocation"]["uri"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![("JIN003", ".env"), ("JIN004", "my%20config/app.json"), ("JIN005", ".env")]
+    );
+
+    let reference = &results[2];
+    assert_eq!(reference["level"], "warning");
+    assert_eq!(reference["ruleIndex"], 4);
+    assert_eq!(reference["locations"][0]["physicalLocation"]["region"]["startLine"], 2);
+    assert_eq!(reference["partialFingerprints"]["jin/v1"], "JIN005:.env:URL");
+    assert!(reference["properties"]["fix"].as_str().unwrap().contains("MISSING"));
+}
@@ -0,0 +1,116 @@
+mod common;
+
+use common::Project;
+use jin_core::schema::{SchemaMapping, SchemasConfig};
+use jin_core::{SchemaRegistry, SourceFormat};
+
+const SERVER_SCHEMA: &str = r#"{
+  "type": "object",
+  "properties": {
+    "server": {
+      "type": "object",
+      "properties": { "port": { "type": "integer" } },
+      "required": ["host"]
+    }
+  },
+  "required": ["server"],
+  "additionalProperties": false
+}"#;
+
+#[test]
+fn validates_files_mapped_in_config() {
+    let dir = Project::new("schema-mapped", &[("schemas/server.json", SERVER_SCHEMA)]);
+    let config = SchemasConfig {
+        mappings: vec![SchemaMapping {
+            pattern: "config/*.yaml".to_string(),
+            schema: "schemas/server.json".to_string(),
+        }],
+        ..SchemasConfig::default()
+    };
+    let mut registry = SchemaRegistry::new(&dir, &config).unwrap();
+
+    let yaml = "server:\n  host: localhost\n  port: \"8080\"\n";
+    let violations = registry.validate("config/app.yaml", &SourceFormat::Yaml, yaml);
+    let unmapped = registry.validate("app.yaml", &SourceFormat::Yaml, yaml);
+
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].path, "server.port");
+    assert_eq!(violations[0].schema, "schemas/server.json");
+    assert_eq!(violations[0].span.map(|s| s.line), Some(3));
+    assert!(unmapped.is_empty());
+}
+
+#[test]
+fn follows_the_schema_named_by_the_file() {
+    let dir = Project::new("schema-declared", &[("config/server.schema.json", SERVER_SCHEMA)]);
+    let mut registry = SchemaRegistry::new(&dir, &SchemasConfig::default()).unwrap();
+
+    // `$schema` itself is not an additional property
+    let json = r#"{"$schema": "./server.schema.json", "server": {"port": 80}}"#;
+    let violations = registry.validate("config/app.json", &SourceFormat::Json, json);
+
+    let yaml = "# yaml-language-server: $schema=./server.schema.json\nserver:\n  host: db\n";
+    let modeline = registry.validate("config/app.yaml", &SourceFormat::Yaml, yaml);
+
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].path, "server");
+    assert!(violations[0].message.contains("host"), "{}", violations[0].message);
+    assert_eq!(violations[0].span.map(|s| s.line), Some(1));
+    assert!(modeline.is_empty());
+}
+
+#[test]
+fn looks_up_schemas_in_the_local_store() {
+    let catalog = r#"{"schemas": [{"name": "Server", "fileMatch": ["server.toml"], "url": "https://example.com/server.json"}]}"#;
+    let dir = Project::new(
+        "schema-store",
+        &[
+            ("schemas/catalog.json", catalog),
+            ("schemas/server.json", SERVER_SCHEMA),
+            ("schemas/app.schema.json", r#"{"required": ["name"]}"#),
+        ],
+    );
+    let config = SchemasConfig {
+        store: Some("schemas".to_string()),
+        ..SchemasConfig::default()
+    };
+    let mut registry = SchemaRegistry::new(&dir, &config).unwrap();
+
+    let from_catalog = registry.validate("server.toml", &SourceFormat::Toml, "[server]\nport = \"x\"\n");
+    let by_name = registry.validate("config/app.json", &SourceFormat::Json, "{}");
+    // Remote schemas are never downloaded
+    let remote = registry.validate(
+        "tsconfig.json",
+        &SourceFormat::Json,
+        r#"{"$schema": "https://json.schemastore.org/tsconfig", "compilerOptions": 1}"#,
+    );
+
+    let paths: Vec<&str> = from_catalog.iter().map(|v| v.path.as_str()).collect();
+    assert_eq!(paths, vec!["server", "server.port"]);
+    assert_eq!(by_name.len(), 1);
+    assert_eq!(by_name[0].schema, "schemas/app.schema.json");
+    assert!(remote.is_empty());
+}
+
+#[test]
+fn scans_validate_the_parsed_documents() {
+    let project = Project::new(
+        "schema-scan",
+        &[
+            ("jin.toml", "[[schemas.mappings]]\npattern = \"config/*\"\nschema = \"schemas/server.json\"\n"),
+            ("schemas/server.json", SERVER_SCHEMA),
+            ("config/app.yaml", "server:\n  host: localhost\n  port: \"8080\"\n"),
+            ("config/app.toml", "[server]\nhost = \"localhost\"\nport = 8080\n"),
+            (".env", "PORT=8080\n"),
+        ],
+    );
+    let (result, _) = project.scan();
+
+    let violations: Vec<(&str, &str, Option<usize>)> = result
+        .issues
+        .schema_violations
+        .iter()
+        .map(|v| (v.file.as_str(), v.path.as_str(), v.span.map(|s| s.line)))
+        .collect();
+    assert_eq!(violations, [("config/app.yaml", "server.port", Some(3))]);
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use common::Project;
+use jin_core::usage::{references, Language};
+use jin_core::ScanResult;
+
+fn keys(content: &str, language: Language) -> Vec<(String, usize, usize)> {
+    references(content, "src/app", language)
+        .into_iter()
+        .map(|r| (r.key, r.span.line, r.span.column))
+        .collect()
+}
+
+fn key_names(content: &str, language: Language) -> Vec<String> {
+    keys(content, language).into_iter().map(|(key, _, _)| key).collect()
+}
+
+#[test]
+fn finds_reads_in_each_language() {
+    let js = "const url = process.env.REDIS_URL;\nconst key = process.env['STRIPE_KEY'];\nconst { A, B: renamed, C = 'x', ...rest } = process.env;\nimport.meta.env.VITE_API;\nprocess.env[name];\n";
+    assert_eq!(
+        keys(js, Language::JavaScript),
+        vec![
+            ("REDIS_URL".to_string(), 1, 25),
+            ("STRIPE_KEY".to_string(), 2, 26),
+            ("A".to_string(), 3, 9),
+            ("B".to_string(), 3, 12),
+            ("C".to_string(), 3, 24),
+            ("VITE_API".to_string(), 4, 17),
+        ]
+    );
+
+    let rust = "let v = env!(\"CARGO_PKG_VERSION\");\nstd::env::var(\"DATABASE_URL\")?;\nenv::var_os(\"HOME\");\noption_env!(\"BUILD\");\n";
+    assert_eq!(key_names(rust, Language::Rust), vec!["CARGO_PKG_VERSION", "DATABASE_URL", "HOME", "BUILD"]);
+
+    let python = "os.environ['A']\nos.environ.get(\"B\", 'x')\nos.getenv('C')\nos.environ[key]\n";
+    assert_eq!(key_names(python, Language::Python), vec!["A", "B", "C"]);
+
+    let go = "host := os.Getenv(\"HOST\")\nport, ok := os.LookupEnv(\"PORT\")\n";
+    assert_eq!(key_names(go, Language::Go), vec!["HOST", "PORT"]);
+}
+
+fn scan(name: &str, files: &[(&str, &str)]) -> ScanResult {
+    Project::new(&format!("usage-{}", name), files).scan().0
+}
+
+#[test]
+fn reports_unused_and_undefined_keys() {
+    let result = scan(
+        "project",
+        &[
+            ("jin.toml", "[code]\nenabled = true\nexclude = [\"vendor/**\"]\n"),
+            (".env", "HOST=db\nDATABASE_URL=postgres://${HOST}/app\nSTRIPE_KEY=sk\nNODE_ENV=dev\n"),
+            ("src/db.ts", "connect(process.env.DATABASE_URL);\n"),
+            ("src/cache.py", "import os\nredis = os.getenv('REDIS_URL')\nmode = os.getenv('NODE_ENV')\n"),
+            ("src/legacy.go", "os.Getenv(\"LEGACY\") // jin-ignore: JIN011\n"),
+            ("vendor/lib.js", "process.env.STRIPE_KEY;\n"),
+        ],
+    );
+
+    let unused: Vec<(&str, &str)> = result
+        .issues
+        .unused_keys
+        .iter()
+        .map(|u| (u.key.as_str(), u.file.as_str()))
+        .collect();
+    // HOST is used by another value, NODE_ENV is on the ignore list
+    assert_eq!(unused, vec![("STRIPE_KEY", ".env")]);
+    assert_eq!(result.issues.unused_keys[0].span.map(|s| s.line), Some(3));
+
+    let undefined: Vec<(&str, &str, Option<usize>)> = result
+        .issues
+        .undefined_keys
+        .iter()
+        .map(|u| (u.key.as_str(), u.file.as_str(), u.span.map(|s| s.line)))
+        .collect();
+    assert_eq!(undefined, vec![("REDIS_URL", "src/cache.py", Some(2))]);
+    assert_eq!(result.summary.suppressed, 1);
+}
+
+#[test]
+fn indexes_nothing_unless_enabled() {
+    let result = scan(
+        "disabled",
+        &[(".env", "STRIPE_KEY=sk\n"), ("src/app.js", "process.env.REDIS_URL;\n")],
+    );
+
+    assert!(result.issues.unused_keys.is_empty());
+    assert!(result.issues.undefined_keys.is_empty());
+}
@@ -0,0 +1,894 @@
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::path::Path;
+
+use jin_core::check::{evaluate, CheckOutcome, CheckReport, IssueCategory, Severity};
+use jin_core::compare::{compare, KeyChange};
+use jin_core::report::render;
+use jin_core::sarif::to_sarif;
+use jin_core::{rename, sync};
+use jin_core::{
+    export_env_example, Baseline, CompareSide, ConfigDiff, DuplicateKind, DuplicateValue, ExportFormat, ExposureKind, FormatRule,
+    InferredType, JinConfig, RenameOptions, ReportFormat, ScanResult, Scanner, SyncOptions, BASELINE_FILE,
+};
+
+// Exit codes for the headless CLI
+const EXIT_OK: i32 = 0;
+const EXIT_FAILED: i32 = 1;
+const EXIT_ERROR: i32 = 2;
+
+// First arguments that switch `jin` into headless mode instead of opening the GUI
+const CLI_ARGS: &[&str] = &["scan", "check", "baseline", "report", "sync", "rename", "diff", "export", "help", "--help", "-h", "--version", "-V"];
+
+#[derive(Parser)]
+#[command(name = "jin", version, about = "Jin - The Config Whisperer. Tame your .envs, calm your configs.")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Scan a directory and list the config files and keys found
+    Scan {
+        #[command(flatten)]
+        scan: ScanArgs,
+        /// Print the full scan result as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Gate on duplicates, missing keys, parse errors and ${VAR} references (exits 1 if any error-level issue is found)
+    Check {
+        #[command(flatten)]
+        scan: ScanArgs,
+        /// Severity of duplicate keys with the same value [default: warn, or [rules] in jin.toml]
+        #[arg(long, value_enum)]
+        duplicates: Option<CliSeverity>,
+        /// Severity of duplicate keys whose values or types differ [default: warn, or [rules] in jin.toml]
+        #[arg(long, value_enum)]
+        value_conflicts: Option<CliSeverity>,
+        /// Severity of keys an .env file lacks compared to its template or peers [default: error, or [rules] in jin.toml]
+        #[arg(long, value_enum)]
+        missing_keys: Option<CliSeverity>,
+        /// Severity of files that fail to parse [default: error, or [rules] in jin.toml]
+        #[arg(long, value_enum)]
+        parse_errors: Option<CliSeverity>,
+        /// Severity of ${VAR} references nothing defines [default: warn, or [rules] in jin.toml]
+        #[arg(long, value_enum)]
+        undefined_references: Option<CliSeverity>,
+        /// Severity of variables that reference each other in a loop [default: error, or [rules] in jin.toml]
+        #[arg(long, value_enum)]
+        reference_cycles: Option<CliSeverity>,
+        /// Severity of secrets in files that git tracks or doesn't ignore [default: error, or [rules] in jin.toml]
+        #[arg(long, value_enum)]
+        exposed_secrets: Option<CliSeverity>,
+        /// Severity of values that don't satisfy the file's JSON Schema [default: error, or [rules] in jin.toml]
+        #[arg(long, value_enum)]
+        schema_violations: Option<CliSeverity>,
+        /// Severity of env keys no source file reads, with --code [default: warn, or [rules] in jin.toml]
+        #[arg(long, value_enum)]
+        unused_keys: Option<CliSeverity>,
+        /// Severity of variables source code reads that no env file defines, with --code [default: warn, or [rules] in jin.toml]
+        #[arg(long, value_enum)]
+        undefined_keys: Option<CliSeverity>,
+        /// Only report issues not recorded in this baseline file (see `jin baseline`)
+        #[arg(long, value_name = "PATH")]
+        baseline: Option<String>,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// Record the current issues in a baseline file, so `jin check --baseline` only reports new ones
+    Baseline {
+        #[command(flatten)]
+        scan: ScanArgs,
+        /// Baseline file to create or refresh (defaults to jin-baseline.json inside DIR)
+        #[arg(long, short)]
+        output: Option<String>,
+    },
+    /// Write a shareable HTML or Markdown report of the scan, with secret values masked
+    Report {
+        #[command(flatten)]
+        scan: ScanArgs,
+        /// Report format
+        #[arg(long, value_enum, default_value_t = CliReportFormat::Html)]
+        format: CliReportFormat,
+        /// Output file (prints to stdout when omitted)
+        #[arg(long, short)]
+        output: Option<String>,
+    },
+    /// Append the keys each .env file is missing, keeping its existing lines as they are
+    Sync {
+        #[command(flatten)]
+        scan: ScanArgs,
+        /// Only update this env file, relative to DIR (repeatable)
+        #[arg(long = "file", value_name = "PATH")]
+        files: Vec<String>,
+        /// Value for keys without a template value
+        #[arg(long, default_value = "")]
+        placeholder: String,
+        /// Use the placeholder even where the template has a value
+        #[arg(long)]
+        no_template_values: bool,
+        /// Print the changes as a unified diff without writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Rename a key in every config file that defines it, keeping the files' formatting
+    Rename {
+        #[command(flatten)]
+        scan: ScanArgs,
+        /// Key to rename (`DB_HOST`, `db.host`, or a table such as `db`)
+        from: String,
+        /// New name
+        to: String,
+        /// Also rename the key's spellings in other formats (`db.host` or `dbHost` for `DB_HOST`)
+        #[arg(long)]
+        cross_format: bool,
+        /// Only rename in this file, relative to DIR (repeatable)
+        #[arg(long = "file", value_name = "PATH")]
+        files: Vec<String>,
+        /// Print the changes as a unified diff without writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Compare two config files, two environments, or two project directories (exits 1 if they differ)
+    Diff {
+        #[command(flatten)]
+        scan: ScanArgs,
+        /// File to compare, relative to DIR, or an environment name with --env [default with --against: every file]
+        left: Option<String>,
+        /// File or environment to compare it with [default with --against: LEFT]
+        right: Option<String>,
+        /// Compare environments (`development`, `production`) rather than files
+        #[arg(long)]
+        env: bool,
+        /// Compare DIR with this directory; keys are matched file by file when no LEFT is given
+        #[arg(long, value_name = "DIR")]
+        against: Option<String>,
+        /// Print the diff as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Export a template containing every discovered key
+    Export {
+        #[command(flatten)]
+        scan: ScanArgs,
+        /// Template format [default: env, or [export] in jin.toml]
+        #[arg(long, value_enum)]
+        format: Option<CliExportFormat>,
+        /// Output file (defaults to .env.example, config.example.json or config.example.yaml inside DIR)
+        #[arg(long, short)]
+        output: Option<String>,
+    },
+}
+
+#[derive(Args, Clone)]
+struct ScanArgs {
+    /// Project directory to scan
+    dir: String,
+    /// Settings file to use instead of DIR/jin.toml
+    #[arg(long, value_name = "PATH")]
+    config: Option<String>,
+    /// Only scan files matching this glob (repeatable)
+    #[arg(long, value_name = "GLOB")]
+    include: Vec<String>,
+    /// Skip files matching this glob (repeatable)
+    #[arg(long, value_name = "GLOB")]
+    exclude: Vec<String>,
+    /// Parse files matching a glob as env, yaml, json or toml, or ignore them (repeatable)
+    #[arg(long = "map", value_name = "GLOB=FORMAT")]
+    format_rules: Vec<FormatRule>,
+    /// Index JS/TS, Rust, Python and Go sources to find unused and undefined keys
+    #[arg(long)]
+    code: bool,
+}
+
+impl ScanArgs {
+    /// Loads jin.toml (or --config) and layers the command-line globs on top of it.
+    fn scanner(self) -> Result<Scanner, String> {
+        let mut config = match &self.config {
+            Some(path) => JinConfig::load(Path::new(path))?,
+            None => JinConfig::discover(&self.dir)?,
+        };
+        config.scan.include.extend(self.include);
+        config.scan.exclude.extend(self.exclude);
+        // Command-line mappings take precedence over the ones from jin.toml
+        config.scan.format_rules.splice(0..0, self.format_rules);
+        if self.code {
+            config.code.enabled = true;
+        }
+
+        Ok(Scanner::new(self.dir).with_config(config))
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum CliSeverity {
+    Off,
+    Warn,
+    Error,
+}
+
+impl From<CliSeverity> for Severity {
+    fn from(severity: CliSeverity) -> Self {
+        match severity {
+            CliSeverity::Off => Severity::Off,
+            CliSeverity::Warn => Severity::Warning,
+            CliSeverity::Error => Severity::Error,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum OutputFormat {
+    Text,
+    Json,
+    /// SARIF 2.1.0, for code scanning integrations
+    Sarif,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum CliExportFormat {
+    Env,
+    Json,
+    Yaml,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum CliReportFormat {
+    /// Self-contained page with the summary, issues and config matrix
+    Html,
+    /// Summary and issues for a pull request comment, with the matrix folded away
+    Markdown,
+}
+
+impl From<CliReportFormat> for ReportFormat {
+    fn from(format: CliReportFormat) -> Self {
+        match format {
+            CliReportFormat::Html => ReportFormat::Html,
+            CliReportFormat::Markdown => ReportFormat::Markdown,
+        }
+    }
+}
+
+impl From<CliExportFormat> for ExportFormat {
+    fn from(format: CliExportFormat) -> Self {
+        match format {
+            CliExportFormat::Env => ExportFormat::Env,
+            CliExportFormat::Json => ExportFormat::Json,
+            CliExportFormat::Yaml => ExportFormat::Yaml,
+        }
+    }
+}
+
+/// Returns true when the process was started with a CLI subcommand rather than as the GUI.
+pub fn is_cli_invocation() -> bool {
+    std::env::args()
+        .nth(1)
+        .map(|arg| CLI_ARGS.contains(&arg.as_str()))
+        .unwrap_or(false)
+}
+
+/// Runs the headless CLI and returns the process exit code.
+pub fn run() -> i32 {
+    let cli = Cli::parse();
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("error: failed to start async runtime: {}", e);
+            return EXIT_ERROR;
+        }
+    };
+
+    match runtime.block_on(execute(cli.command)) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            EXIT_ERROR
+        }
+    }
+}
+
+async fn execute(command: Command) -> Result<i32, String> {
+    match command {
+        Command::Scan { scan, json } => {
+            let result = scan.scanner()?.scan().await?;
+            if json {
+                print_json(&result)?;
+            } else {
+                print_scan(&result);
+            }
+            Ok(EXIT_OK)
+        }
+        Command::Check {
+            scan,
+            duplicates,
+            value_conflicts,
+            missing_keys,
+            parse_errors,
+            undefined_references,
+            reference_cycles,
+            exposed_secrets,
+            schema_violations,
+            unused_keys,
+            undefined_keys,
+            baseline,
+            format,
+        } => {
+            let mut scanner = scan.scanner()?;
+            if let Some(path) = baseline {
+                let path = Path::new(&path);
+                if !path.exists() {
+                    return Err(format!(
+                        "baseline {} does not exist; run `jin baseline` to create it",
+                        path.display()
+                    ));
+                }
+                scanner = scanner.with_baseline(Baseline::load(path)?);
+            }
+            let mut policy = scanner.config().rules.clone();
+            if let Some(severity) = duplicates {
+                policy.duplicates = severity.into();
+            }
+            if let Some(severity) = value_conflicts {
+                policy.value_conflicts = severity.into();
+            }
+            if let Some(severity) = missing_keys {
+                policy.missing_keys = severity.into();
+            }
+            if let Some(severity) = parse_errors {
+                policy.parse_errors = severity.into();
+            }
+            if let Some(severity) = undefined_references {
+                policy.undefined_references = severity.into();
+            }
+            if let Some(severity) = reference_cycles {
+                policy.reference_cycles = severity.into();
+            }
+            if let Some(severity) = exposed_secrets {
+                policy.exposed_secrets = severity.into();
+            }
+            if let Some(severity) = schema_violations {
+                policy.schema_violations = severity.into();
+            }
+            if let Some(severity) = unused_keys {
+                policy.unused_keys = severity.into();
+            }
+            if let Some(severity) = undefined_keys {
+                policy.undefined_keys = severity.into();
+            }
+            // The issue list and the baseline match use the same severities as the verdict
+            let config = JinConfig {
+                rules: policy.clone(),
+                ..scanner.config().clone()
+            };
+            scanner = scanner.with_config(config);
+
+            let result = scanner.scan().await?;
+            let outcome = evaluate(&result, &policy);
+            let exit_code = if outcome.passed { EXIT_OK } else { EXIT_FAILED };
+
+            match format {
+                OutputFormat::Text => print_issues(&result, &outcome),
+                OutputFormat::Json => print_json(&CheckReport { outcome, result })?,
+                OutputFormat::Sarif => print_json(&to_sarif(&result, &policy))?,
+            }
+            Ok(exit_code)
+        }
+        Command::Baseline { scan, output } => {
+            let scanner = scan.scanner()?;
+            let output = output.unwrap_or_else(|| {
+                Path::new(scanner.root()).join(BASELINE_FILE).to_string_lossy().to_string()
+            });
+
+            let result = scanner.scan().await?;
+            Baseline::from_issues(&result.diagnostics).save(Path::new(&output))?;
+            println!("Recorded {} issues in {}", result.diagnostics.len(), output);
+            Ok(EXIT_OK)
+        }
+        Command::Report { scan, format, output } => {
+            let result = scan.scanner()?.scan().await?;
+            let report = render(&result, format.into());
+            match output {
+                Some(output) => {
+                    std::fs::write(&output, report)
+                        .map_err(|e| format!("Failed to write {}: {}", output, e))?;
+                    println!("Wrote report to {}", output);
+                }
+                None => print!("{}", report),
+            }
+            Ok(EXIT_OK)
+        }
+        Command::Sync {
+            scan,
+            files,
+            placeholder,
+            no_template_values,
+            dry_run,
+        } => {
+            let scanner = scan.scanner()?;
+            let result = scanner.scan().await?;
+            let options = SyncOptions {
+                files,
+                placeholder,
+                template_values: !no_template_values,
+            };
+            let edits = sync::plan(&result, scanner.config(), &options)?;
+            if edits.is_empty() {
+                println!("No missing keys to add");
+                return Ok(EXIT_OK);
+            }
+
+            let keys: usize = edits.iter().map(|edit| edit.added.len()).sum();
+            if dry_run {
+                for edit in &edits {
+                    print!("{}", edit.diff);
+                }
+                println!("Would add {} keys to {} files", keys, edits.len());
+            } else {
+                sync::apply(&result.root, &edits)?;
+                for edit in &edits {
+                    println!("  {:<48} +{} keys", edit.file, edit.added.len());
+                }
+                println!("Added {} keys to {} files", keys, edits.len());
+            }
+            Ok(EXIT_OK)
+        }
+        Command::Rename {
+            scan,
+            from,
+            to,
+            cross_format,
+            files,
+            dry_run,
+        } => {
+            let scanner = scan.scanner()?;
+            let result = scanner.scan().await?;
+            let options = RenameOptions {
+                from,
+                to,
+                cross_format,
+                files,
+            };
+            let plan = rename::plan(&result, scanner.config(), &options)?;
+            if plan.changes.is_empty() && plan.conflicts.is_empty() {
+                println!("No file defines {}", options.from);
+                return Ok(EXIT_OK);
+            }
+
+            if dry_run {
+                for change in &plan.changes {
+                    print!("{}", change.edit.diff);
+                }
+                for conflict in &plan.conflicts {
+                    eprintln!("  can't rename in {}", conflict);
+                }
+                println!("Would rename in {} files", plan.changes.len());
+                return Ok(EXIT_OK);
+            }
+            // A rename that misses some files would leave the project half renamed
+            if !plan.conflicts.is_empty() {
+                for conflict in &plan.conflicts {
+                    eprintln!("  can't rename in {}", conflict);
+                }
+                return Err(format!(
+                    "{} files can't be renamed; nothing was written",
+                    plan.conflicts.len()
+                ));
+            }
+            rename::apply(&result.root, &plan.changes)?;
+            for change in &plan.changes {
+                for renamed in &change.renamed {
+                    println!("  {:<48} {} -> {}", change.edit.file, renamed.from, renamed.to);
+                }
+            }
+            println!("Renamed {} in {} files", options.from, plan.changes.len());
+            Ok(EXIT_OK)
+        }
+        Command::Diff {
+            scan,
+            left,
+            right,
+            env,
+            against,
+            json,
+        } => {
+            let side = |name: Option<String>| match name {
+                Some(name) if env => CompareSide::Environment { name },
+                Some(path) => CompareSide::File { path },
+                None => CompareSide::All,
+            };
+            let (left_side, right_side) = match (&against, left, right) {
+                (Some(_), left, right) => {
+                    let right = right.or_else(|| left.clone());
+                    (side(left), side(right))
+                }
+                (None, Some(left), Some(right)) => (side(Some(left)), side(Some(right))),
+                (None, _, _) => {
+                    return Err(
+                        "give two files (or two environments with --env) to compare, or a directory with --against"
+                            .to_string(),
+                    )
+                }
+            };
+            let other = match against {
+                Some(dir) => Some(ScanArgs { dir, ..scan.clone() }.scanner()?.scan().await?),
+                None => None,
+            };
+            let left_result = scan.scanner()?.scan().await?;
+            let right_result = other.as_ref().unwrap_or(&left_result);
+
+            let diff = compare(&left_result, &left_side, right_result, &right_side)?;
+            if json {
+                print_json(&diff)?;
+            } else {
+                println!(
+                    "Comparing {} with {}",
+                    side_label(&left_result, &left_side),
+                    side_label(right_result, &right_side)
+                );
+                print_diff(&diff);
+            }
+            // Like diff(1): differences fail the command
+            Ok(if diff.is_empty() { EXIT_OK } else { EXIT_FAILED })
+        }
+        Command::Export { scan, format, output } => {
+            let scanner = scan.scanner()?;
+            let export = &scanner.config().export;
+            let format: ExportFormat = format.map(Into::into).unwrap_or(export.format);
+            // --output is taken as given; jin.toml and default outputs resolve against the project root
+            let output = output.unwrap_or_else(|| {
+                let name = export
+                    .output
+                    .clone()
+                    .unwrap_or_else(|| format.default_file_name().to_string());
+                Path::new(scanner.root()).join(name).to_string_lossy().to_string()
+            });
+
+            let result = scanner.scan().await?;
+            let written = export_env_example(&output, &result.entries, format).await?;
+            println!("Exported {} keys to {}", result.summary.unique_keys, written);
+            Ok(EXIT_OK)
+        }
+    }
+}
+
+fn print_json<T: serde::Serialize>(value: &T) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|e| format!("JSON serialization error: {}", e))?;
+    println!("{}", json);
+    Ok(())
+}
+
+fn print_summary(result: &ScanResult) {
+    println!(
+        "Scanned {} files in {}: {} keys ({} unique)",
+        result.summary.total_files,
+        result.root,
+        result.summary.total_keys,
+        result.summary.unique_keys
+    );
+}
+
+fn print_scan(result: &ScanResult) {
+    print_summary(result);
+
+    let mut files: Vec<_> = result.files.iter().collect();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    for file in files {
+        println!("  {:<48} {:>4} keys", file.path, file.count);
+    }
+}
+
+fn severity_label(outcome: &CheckOutcome, category: IssueCategory) -> &'static str {
+    let severity = outcome
+        .categories
+        .iter()
+        .find(|c| c.category == category)
+        .map(|c| c.severity)
+        .unwrap_or(Severity::Off);
+    severity_name(severity)
+}
+
+fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Off => "ignored",
+        Severity::Warning => "warning",
+        Severity::Error => "error",
+    }
+}
+
+// Names the file, plus the key's spelling there when it differs from the reported one
+fn spelled_file(value: &DuplicateValue, key: &str) -> String {
+    if value.key == key {
+        value.file.clone()
+    } else {
+        format!("{} ({})", value.file, value.key)
+    }
+}
+
+fn type_name(inferred_type: InferredType) -> &'static str {
+    match inferred_type {
+        InferredType::String => "string",
+        InferredType::Number => "number",
+        InferredType::Boolean => "boolean",
+        InferredType::Null => "null",
+        InferredType::Unknown => "unknown",
+    }
+}
+
+fn side_label(result: &ScanResult, side: &CompareSide) -> String {
+    match side {
+        CompareSide::File { path } => path.clone(),
+        CompareSide::Environment { name } => format!("the {} environment", name),
+        CompareSide::All => result.root.clone(),
+    }
+}
+
+// `file: KEY` when whole projects are compared, otherwise the key as spelled on each side
+fn change_label(change: &KeyChange) -> String {
+    let key = match (&change.left, &change.right) {
+        (Some(left), Some(right)) if left.key != right.key => format!("{} ({})", left.key, right.key),
+        _ => change.key.clone(),
+    };
+    match &change.file {
+        Some(file) => format!("{}: {}", file, key),
+        None => key,
+    }
+}
+
+fn side_value(value: &Option<DuplicateValue>) -> &str {
+    value.as_ref().and_then(|v| v.value.as_deref()).unwrap_or("null")
+}
+
+fn print_diff(diff: &ConfigDiff) {
+    for change in &diff.added {
+        println!("  + {} = {}", change_label(change), side_value(&change.right));
+    }
+    for change in &diff.removed {
+        println!("  - {} = {}", change_label(change), side_value(&change.left));
+    }
+    for change in &diff.changed_value {
+        println!(
+            "  ~ {}: {} -> {}",
+            change_label(change),
+            side_value(&change.left),
+            side_value(&change.right)
+        );
+    }
+    for change in &diff.changed_type {
+        let type_of = |value: &Option<DuplicateValue>| value.as_ref().map(|v| type_name(v.inferred_type)).unwrap_or("unknown");
+        println!(
+            "  ! {}: {} ({}) -> {} ({})",
+            change_label(change),
+            side_value(&change.left),
+            type_of(&change.left),
+            side_value(&change.right),
+            type_of(&change.right)
+        );
+    }
+    println!(
+        "{} added, {} removed, {} changed, {} changed type, {} unchanged",
+        diff.added.len(),
+        diff.removed.len(),
+        diff.changed_value.len(),
+        diff.changed_type.len(),
+        diff.unchanged
+    );
+}
+
+fn print_issues(result: &ScanResult, outcome: &CheckOutcome) {
+    print_summary(result);
+    if result.summary.suppressed > 0 {
+        println!("{} issues suppressed", result.summary.suppressed);
+    }
+    if result.summary.baselined > 0 {
+        println!("{} known issues in the baseline", result.summary.baselined);
+    }
+
+    let issues = &result.issues;
+    if !issues.duplicates.is_empty() {
+        println!(
+            "\nDuplicate keys ({}) [{}]:",
+            issues.duplicates.len(),
+            severity_label(outcome, IssueCategory::Duplicates)
+        );
+        for duplicate in &issues.duplicates {
+            match duplicate.kind {
+                DuplicateKind::SameValue => {
+                    let files: Vec<String> = duplicate
+                        .values
+                        .iter()
+                        .map(|value| spelled_file(value, &duplicate.key))
+                        .collect();
+                    println!("  {}: {} (same value)", duplicate.key, files.join(", "));
+                }
+                DuplicateKind::DifferentValues | DuplicateKind::DifferentTypes => {
+                    let conflict = if duplicate.kind == DuplicateKind::DifferentTypes {
+                        "different types"
+                    } else {
+                        "different values"
+                    };
+                    println!(
+                        "  {}: {} [{}]",
+                        duplicate.key,
+                        conflict,
+                        severity_label(outcome, IssueCategory::ValueConflicts)
+                    );
+                    for value in &duplicate.values {
+                        println!(
+                            "    {}: {} ({})",
+                            spelled_file(value, &duplicate.key),
+                            value.value.as_deref().unwrap_or("null"),
+                            type_name(value.inferred_type)
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    if !issues.missing_by_env_file.is_empty() {
+        println!(
+            "\nMissing keys ({} files) [{}]:",
+            issues.missing_by_env_file.len(),
+            severity_label(outcome, IssueCategory::MissingKeys)
+        );
+        let mut missing: Vec<_> = issues.missing_by_env_file.iter().collect();
+        missing.sort_by(|a, b| a.file.cmp(&b.file));
+        for entry in missing {
+            let environment = entry
+                .environment
+                .as_ref()
+                .map(|name| format!("{}, ", name))
+                .unwrap_or_default();
+            println!(
+                "  {} ({}vs {}): {}",
+                entry.file,
+                environment,
+                entry.compared_to.join(", "),
+                entry.missing_keys.join(", ")
+            );
+        }
+    }
+
+    if !issues.parse_errors.is_empty() {
+        println!(
+            "\nParse errors ({}) [{}]:",
+            issues.parse_errors.len(),
+            severity_label(outcome, IssueCategory::ParseErrors)
+        );
+        for error in &issues.parse_errors {
+            match error.span {
+                Some(span) => println!("  {}:{}:{}: {}", error.file, span.line, span.column, error.message),
+                None => println!("  {}: {}", error.file, error.message),
+            }
+        }
+    }
+
+    if !issues.undefined_references.is_empty() {
+        println!(
+            "\nUndefined references ({}) [{}]:",
+            issues.undefined_references.len(),
+            severity_label(outcome, IssueCategory::UndefinedReferences)
+        );
+        for reference in &issues.undefined_references {
+            let location = match reference.span {
+                Some(span) => format!("{}:{}:{}", reference.file, span.line, span.column),
+                None => reference.file.clone(),
+            };
+            println!("  {}: {} uses ${{{}}}", location, reference.key, reference.reference);
+        }
+    }
+
+    if !issues.reference_cycles.is_empty() {
+        println!(
+            "\nReference cycles ({}) [{}]:",
+            issues.reference_cycles.len(),
+            severity_label(outcome, IssueCategory::ReferenceCycles)
+        );
+        for cycle in &issues.reference_cycles {
+            let mut keys = cycle.keys.clone();
+            if let Some(first) = cycle.keys.first() {
+                keys.push(first.clone());
+            }
+            println!("  {}: {}", cycle.file, keys.join(" -> "));
+        }
+    }
+
+    if !issues.exposed_secrets.is_empty() {
+        println!(
+            "\nExposed secrets ({}) [{}]:",
+            issues.exposed_secrets.len(),
+            severity_label(outcome, IssueCategory::ExposedSecrets)
+        );
+        for exposure in &issues.exposed_secrets {
+            let reason = match exposure.kind {
+                ExposureKind::Tracked => "tracked by git",
+                ExposureKind::NotIgnored => "not gitignored",
+            };
+            println!("  {} ({}):", exposure.file, reason);
+            for secret in &exposure.secrets {
+                let line = secret.span.map(|s| format!(":{}", s.line)).unwrap_or_default();
+                match &secret.rule {
+                    Some(rule) => println!("    {}{} ({})", secret.key, line, rule),
+                    None => println!("    {}{}", secret.key, line),
+                }
+            }
+        }
+    }
+
+    if !issues.schema_violations.is_empty() {
+        println!(
+            "\nSchema violations ({}) [{}]:",
+            issues.schema_violations.len(),
+            severity_label(outcome, IssueCategory::SchemaViolations)
+        );
+        for violation in &issues.schema_violations {
+            let location = match violation.span {
+                Some(span) => format!("{}:{}:{}", violation.file, span.line, span.column),
+                None => violation.file.clone(),
+            };
+            if violation.path.is_empty() {
+                println!("  {}: {}", location, violation.message);
+            } else {
+                println!("  {}: {}: {}", location, violation.path, violation.message);
+            }
+        }
+    }
+
+    if !issues.policy_violations.is_empty() {
+        println!("\nPolicy violations ({}):", issues.policy_violations.len());
+        for violation in &issues.policy_violations {
+            let location = match violation.span {
+                Some(span) => format!("{}:{}:{}", violation.file, span.line, span.column),
+                None => violation.file.clone(),
+            };
+            println!(
+                "  {}: {} [{}, {}]",
+                location,
+                violation.message,
+                violation.policy,
+                severity_name(violation.severity)
+            );
+        }
+    }
+
+    if !issues.unused_keys.is_empty() {
+        println!(
+            "\nUnused keys ({}) [{}]:",
+            issues.unused_keys.len(),
+            severity_label(outcome, IssueCategory::UnusedKeys)
+        );
+        for unused in &issues.unused_keys {
+            let location = match unused.span {
+                Some(span) => format!("{}:{}:{}", unused.file, span.line, span.column),
+                None => unused.file.clone(),
+            };
+            println!("  {}: {} is never read", location, unused.key);
+        }
+    }
+
+    if !issues.undefined_keys.is_empty() {
+        println!(
+            "\nUndefined keys ({}) [{}]:",
+            issues.undefined_keys.len(),
+            severity_label(outcome, IssueCategory::UndefinedKeys)
+        );
+        for undefined in &issues.undefined_keys {
+            let location = match undefined.span {
+                Some(span) => format!("{}:{}:{}", undefined.file, span.line, span.column),
+                None => undefined.file.clone(),
+            };
+            println!("  {}: {} is not defined in any env file", location, undefined.key);
+        }
+    }
+
+    println!(
+        "\n{}: {} errors, {} warnings",
+        if outcome.passed { "Check passed" } else { "Check failed" },
+        outcome.errors,
+        outcome.warnings
+    );
+}
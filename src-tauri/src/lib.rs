@@ -1,128 +1,92 @@
 // Prevents additional console window on Windows in release
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-mod analyzer;
-mod exporter;
-mod parser;
-mod scanner;
-mod types;
+pub mod cli;
 mod watcher;
 
-use analyzer::analyze_entries;
-use exporter::export_env_example;
-use parser::parse_file;
-use scanner::scan_directory;
-use types::{ExportFormat, FileCache, NormalizedEntry, ParseError, ScanCacheState, ScanResult, ScanSummary};
+use jin_core::rename::RenameChange;
+use jin_core::{compare, edit, rename, sync};
+use jin_core::{
+    export_env_example, CompareSide, ConfigDiff, ExportFormat, FileEdit, KeyEdit, NormalizedEntry, RenameOptions, RenamePlan, ScanCacheState,
+    ScanOptions, ScanResult, Scanner, SourceFormat, SyncEdit, SyncOptions,
+};
 use watcher::{FileWatcherState, start_watching, stop_watching, get_watching_status};
-use std::sync::Arc;
-use tokio::sync::Semaphore;
 
 #[tauri::command]
 async fn scan_folder(
     path: String,
+    options: Option<ScanOptions>,
     cache_state: tauri::State<'_, ScanCacheState>
 ) -> Result<ScanResult, String> {
-    // Step 1: Discover files
-    let mut files = scan_directory(&path)?;
-
-    // Step 2: Create semaphore to limit concurrent file operations (16 concurrent tasks)
-    let semaphore = Arc::new(Semaphore::new(16));
-
-    // Step 3: Parse files in parallel with caching and concurrency limiting
-    let mut parse_tasks = Vec::new();
-    for file in &files {
-        let path_clone = path.clone();
-        let file_path = file.path.clone();
-        let file_format = file.format.clone();
-        let sem = Arc::clone(&semaphore);
-        let cache = (*cache_state).clone();
-
-        parse_tasks.push(tokio::spawn(async move {
-            // Acquire semaphore permit (limits concurrency)
-            let _permit = sem.acquire().await.unwrap();
-
-            // Try to get file metadata for caching - if it fails, just skip caching
-            let modified_time_opt = match tokio::fs::metadata(&file_path).await {
-                Ok(metadata) => metadata.modified().ok(),
-                Err(_) => None,
-            };
-
-            // Check cache if we have a modification time
-            if let Some(modified_time) = modified_time_opt {
-                if let Some(cached) = cache.get(&file_path) {
-                    // If modification time matches, use cached entries
-                    if cached.modified_time == modified_time {
-                        return Ok(cached.entries);
-                    }
-                }
-            }
-
-            // Cache miss, file changed, or no metadata - parse the file
-            let result = parse_file(&path_clone, &file_path, &file_format).await;
-
-            // Update cache if parsing succeeded and we have a modification time
-            if let (Ok(ref entries), Some(modified_time)) = (&result, modified_time_opt) {
-                cache.insert(
-                    file_path.clone(),
-                    FileCache {
-                        modified_time,
-                        entries: entries.clone(),
-                    },
-                );
-            }
-
-            result
-        }));
+    let mut scanner = Scanner::discover(path)?.with_cache((*cache_state).clone());
+    if let Some(options) = options {
+        scanner = scanner.with_options(options);
     }
+    scanner.scan().await
+}
 
-    // Wait for all parse tasks to complete
-    let mut all_entries: Vec<NormalizedEntry> = Vec::new();
-    let mut parse_errors: Vec<ParseError> = Vec::new();
+#[tauri::command]
+async fn export_env_example_cmd(output_path: String, entries: Vec<NormalizedEntry>, format: ExportFormat) -> Result<String, String> {
+    export_env_example(&output_path, &entries, format).await
+}
 
-    for (idx, task) in parse_tasks.into_iter().enumerate() {
-        match task.await {
-            Ok(Ok(entries)) => {
-                files[idx].count = entries.len();
-                all_entries.extend(entries);
-            }
-            Ok(Err(err)) => {
-                parse_errors.push(err);
-            }
-            Err(e) => {
-                parse_errors.push(ParseError {
-                    file: files[idx].path.clone(),
-                    message: format!("Task failed: {}", e),
-                });
-            }
-        }
-    }
+#[tauri::command]
+async fn plan_sync_cmd(
+    path: String,
+    options: SyncOptions,
+    cache_state: tauri::State<'_, ScanCacheState>
+) -> Result<Vec<SyncEdit>, String> {
+    let scanner = Scanner::discover(path)?.with_cache((*cache_state).clone());
+    let result = scanner.scan().await?;
+    sync::plan(&result, scanner.config(), &options)
+}
 
-    // Step 4: Analyze for issues
-    let mut issues = analyze_entries(&all_entries);
-    issues.parse_errors = parse_errors;
+#[tauri::command]
+fn apply_sync_cmd(path: String, edits: Vec<SyncEdit>) -> Result<(), String> {
+    sync::apply(&path, &edits)
+}
 
-    // Step 5: Generate summary
-    let unique_keys: std::collections::HashSet<String> =
-        all_entries.iter().map(|e| e.key.clone()).collect();
+#[tauri::command]
+fn plan_key_edits_cmd(path: String, file: String, format: SourceFormat, edits: Vec<KeyEdit>) -> Result<FileEdit, String> {
+    edit::plan(&path, &file, &format, &edits)
+}
 
-    let summary = ScanSummary {
-        total_files: files.len(),
-        total_keys: all_entries.len(),
-        unique_keys: unique_keys.len(),
-    };
+#[tauri::command]
+fn apply_key_edits_cmd(path: String, edits: Vec<FileEdit>) -> Result<(), String> {
+    edit::apply(&path, &edits)
+}
+
+#[tauri::command]
+async fn plan_rename_cmd(
+    path: String,
+    options: RenameOptions,
+    cache_state: tauri::State<'_, ScanCacheState>
+) -> Result<RenamePlan, String> {
+    let scanner = Scanner::discover(path)?.with_cache((*cache_state).clone());
+    let result = scanner.scan().await?;
+    rename::plan(&result, scanner.config(), &options)
+}
 
-    Ok(ScanResult {
-        root: path,
-        files,
-        entries: all_entries,
-        issues,
-        summary,
-    })
+#[tauri::command]
+fn apply_rename_cmd(path: String, changes: Vec<RenameChange>) -> Result<(), String> {
+    rename::apply(&path, &changes)
 }
 
 #[tauri::command]
-async fn export_env_example_cmd(output_path: String, entries: Vec<NormalizedEntry>, format: ExportFormat) -> Result<String, String> {
-    export_env_example(&output_path, &entries, format).await
+async fn compare_cmd(
+    path: String,
+    left: CompareSide,
+    right: CompareSide,
+    other_path: Option<String>,
+    cache_state: tauri::State<'_, ScanCacheState>
+) -> Result<ConfigDiff, String> {
+    let result = Scanner::discover(path)?.with_cache((*cache_state).clone()).scan().await?;
+    // The right side comes from a second project when one is given
+    let other = match other_path {
+        Some(other_path) => Some(Scanner::discover(other_path)?.with_cache((*cache_state).clone()).scan().await?),
+        None => None,
+    };
+    compare::compare(&result, &left, other.as_ref().unwrap_or(&result), &right)
 }
 
 #[tauri::command]
@@ -154,6 +118,13 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             scan_folder,
             export_env_example_cmd,
+            plan_sync_cmd,
+            apply_sync_cmd,
+            plan_key_edits_cmd,
+            apply_key_edits_cmd,
+            plan_rename_cmd,
+            apply_rename_cmd,
+            compare_cmd,
             start_file_watching,
             stop_file_watching,
             get_file_watching_status
@@ -2,5 +2,31 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+    // `jin scan|check|export ...` runs headless without creating a window
+    if jin_lib::cli::is_cli_invocation() {
+        attach_console();
+        std::process::exit(jin_lib::cli::run());
+    }
+
     jin_lib::run()
 }
+
+// A GUI-subsystem binary starts without a console, so the CLI's output would go nowhere.
+// Attaching to the console of the shell that started it makes stdout and stderr reach it.
+#[cfg(windows)]
+fn attach_console() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+
+    // Fails harmlessly when there is no parent console, or one is already attached (debug builds)
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(windows))]
+fn attach_console() {}
@@ -6,6 +6,8 @@ use notify_debouncer_full::{
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
+use jin_core::usage::Language;
+use jin_core::{JinConfig, CONFIG_FILE, JIN_IGNORE_FILE};
 use tauri::{AppHandle, Emitter};
 
 pub struct FileWatcherState {
@@ -33,9 +35,14 @@ pub fn start_watching(
     let path_clone = path.clone();
     let app_handle_clone = app_handle.clone();
 
-    // Create debouncer with 1 second delay
+    // Debounce delay comes from [watch] in jin.toml; an invalid file is reported by the next scan
+    let config = JinConfig::discover(&path).unwrap_or_default();
+    let debounce_ms = config.watch.debounce_ms;
+    // Source files only matter when the scan indexes them for unused and undefined keys
+    let watch_sources = config.code.enabled;
+
     let mut debouncer = new_debouncer(
-        Duration::from_secs(1),
+        Duration::from_millis(debounce_ms),
         None,
         move |result: DebounceEventResult| {
             match result {
@@ -45,10 +52,13 @@ pub fn start_watching(
                         event.paths.iter().any(|path| {
                             let path_str = path.to_string_lossy();
                             path_str.contains(".env")
+                                || path_str.ends_with(JIN_IGNORE_FILE)
+                                || path_str.ends_with(CONFIG_FILE)
                                 || path_str.ends_with(".yaml")
                                 || path_str.ends_with(".yml")
                                 || path_str.ends_with(".json")
                                 || path_str.ends_with(".toml")
+                                || (watch_sources && Language::from_path(path).is_some())
                         })
                     });
 
@@ -9,9 +9,11 @@ import { IssuesPanel } from "./components/IssuesPanel";
 import { ConfigMatrix } from "./components/ConfigMatrix";
 import { Footer } from "./components/Footer";
 import { OnboardingTour } from "./components/OnboardingTour";
+import { SyncDialog } from "./components/SyncDialog";
+import { RenameDialog } from "./components/RenameDialog";
 import { useScan } from "./store/useScan";
 import { useFileWatcher } from "./store/useFileWatcher";
-import type { ScanResult } from "./types";
+import type { RenameOptions, RenamePlan, ScanResult, SyncEdit, SyncOptions } from "./types";
 
 const TOUR_COMPLETED_KEY = "jin-onboarding-tour-completed";
 
@@ -31,6 +33,16 @@ function App() {
   const [isTourOpen, setIsTourOpen] = useState(false);
   const [shouldShowTour, setShouldShowTour] = useState(false);
 
+  // Missing-key write-back preview
+  const [syncEdits, setSyncEdits] = useState<SyncEdit[] | null>(null);
+  const [isApplyingSync, setIsApplyingSync] = useState(false);
+
+  // Bulk rename preview
+  const [renameKey, setRenameKey] = useState<string | null>(null);
+  const [renameSpellings, setRenameSpellings] = useState<string[]>([]);
+  const [renamePlan, setRenamePlan] = useState<RenamePlan | null>(null);
+  const [isRenaming, setIsRenaming] = useState(false);
+
   // File watcher state
   const isWatching = useFileWatcher((state) => state.isWatching);
   const setWatching = useFileWatcher((state) => state.setWatching);
@@ -40,7 +52,14 @@ function App() {
     () =>
       issues.duplicates.length +
       issues.missingByEnvFile.length +
-      issues.parseErrors.length,
+      issues.parseErrors.length +
+      issues.undefinedReferences.length +
+      issues.referenceCycles.length +
+      issues.exposedSecrets.length +
+      issues.schemaViolations.length +
+      issues.policyViolations.length +
+      issues.unusedKeys.length +
+      issues.undefinedKeys.length,
     [issues]
   );
 
@@ -55,7 +74,14 @@ function App() {
       const { totalFiles, uniqueKeys } = result.summary;
       const issueCount = result.issues.duplicates.length +
                          result.issues.missingByEnvFile.length +
-                         result.issues.parseErrors.length;
+                         result.issues.parseErrors.length +
+                         result.issues.undefinedReferences.length +
+                         result.issues.referenceCycles.length +
+                         result.issues.exposedSecrets.length +
+                         result.issues.schemaViolations.length +
+                         result.issues.policyViolations.length +
+                         result.issues.unusedKeys.length +
+                         result.issues.undefinedKeys.length;
 
       if (issueCount > 0) {
         toast.warning(`Found ${issueCount} issue${issueCount === 1 ? '' : 's'} across ${totalFiles} config files`, {
@@ -178,6 +204,116 @@ function App() {
     }
   }, [projectPath, entries]);
 
+  const handleAddMissingKeys = useCallback(async (file: string) => {
+    if (!projectPath) {
+      return;
+    }
+
+    try {
+      const options: SyncOptions = { files: [file], placeholder: "", templateValues: true };
+      const edits = await invoke<SyncEdit[]>("plan_sync_cmd", { path: projectPath, options });
+      if (edits.length === 0) {
+        toast.info("Nothing to add", {
+          description: `${file} already has every expected key`
+        });
+        return;
+      }
+      setSyncEdits(edits);
+    } catch (err) {
+      const errorMessage = err instanceof Error ? err.message : String(err);
+      toast.error("Failed to prepare changes", {
+        description: errorMessage
+      });
+    }
+  }, [projectPath]);
+
+  const handleApplySync = useCallback(async () => {
+    if (!projectPath || !syncEdits) {
+      return;
+    }
+
+    try {
+      setIsApplyingSync(true);
+      await invoke("apply_sync_cmd", { path: projectPath, edits: syncEdits });
+      const keyCount = syncEdits.reduce((sum, edit) => sum + edit.added.length, 0);
+      toast.success("Missing keys added", {
+        description: `Added ${keyCount} key${keyCount === 1 ? '' : 's'} to ${syncEdits.map((edit) => edit.file).join(", ")}`
+      });
+      setSyncEdits(null);
+      await scanFolder(projectPath);
+    } catch (err) {
+      const errorMessage = err instanceof Error ? err.message : String(err);
+      toast.error("Failed to add missing keys", {
+        description: errorMessage
+      });
+    } finally {
+      setIsApplyingSync(false);
+    }
+  }, [projectPath, syncEdits, scanFolder]);
+
+  const handleSyncDialogChange = useCallback((open: boolean) => {
+    if (!open) {
+      setSyncEdits(null);
+    }
+  }, []);
+
+  const handleRenameKey = useCallback((key: string, spellings: string[]) => {
+    setRenameKey(key);
+    setRenameSpellings(spellings);
+    setRenamePlan(null);
+  }, []);
+
+  const handlePreviewRename = useCallback(async (to: string, crossFormat: boolean) => {
+    if (!projectPath || !renameKey) {
+      return;
+    }
+
+    try {
+      setIsRenaming(true);
+      const options: RenameOptions = { from: renameKey, to, crossFormat, files: [] };
+      setRenamePlan(await invoke<RenamePlan>("plan_rename_cmd", { path: projectPath, options }));
+    } catch (err) {
+      const errorMessage = err instanceof Error ? err.message : String(err);
+      toast.error("Failed to prepare rename", {
+        description: errorMessage
+      });
+    } finally {
+      setIsRenaming(false);
+    }
+  }, [projectPath, renameKey]);
+
+  const handleApplyRename = useCallback(async () => {
+    if (!projectPath || !renamePlan) {
+      return;
+    }
+
+    try {
+      setIsRenaming(true);
+      await invoke("apply_rename_cmd", { path: projectPath, changes: renamePlan.changes });
+      const fileCount = renamePlan.changes.length;
+      toast.success("Key renamed", {
+        description: `Renamed ${renameKey} in ${fileCount} file${fileCount === 1 ? '' : 's'}`
+      });
+      setRenameKey(null);
+      setRenamePlan(null);
+      await scanFolder(projectPath);
+    } catch (err) {
+      const errorMessage = err instanceof Error ? err.message : String(err);
+      toast.error("Failed to rename key", {
+        description: errorMessage
+      });
+    } finally {
+      setIsRenaming(false);
+    }
+  }, [projectPath, renameKey, renamePlan, scanFolder]);
+
+  const handleRenameDialogChange = useCallback((open: boolean) => {
+    if (!open) {
+      setRenameKey(null);
+      setRenamePlan(null);
+    }
+  }, []);
+
   // Check if user has completed the tour
   useEffect(() => {
     const tourCompleted = localStorage.getItem(TOUR_COMPLETED_KEY);
@@ -315,11 +451,12 @@ function App() {
       />
 
       <div className="flex flex-col md:flex-row flex-1 overflow-hidden">
-        <IssuesPanel issues={issues} />
+        <IssuesPanel issues={issues} onAddMissingKeys={handleAddMissingKeys} />
         <ConfigMatrix
           entries={entries}
           files={files}
           onRescan={handleRescan}
+          onRenameKey={handleRenameKey}
         />
       </div>
 
@@ -332,6 +469,23 @@ function App() {
 
       <OnboardingTour isOpen={isTourOpen} onClose={handleTourClose} />
 
+      <SyncDialog
+        edits={syncEdits}
+        isApplying={isApplyingSync}
+        onApply={handleApplySync}
+        onOpenChange={handleSyncDialogChange}
+      />
+
+      <RenameDialog
+        fromKey={renameKey}
+        spellings={renameSpellings}
+        plan={renamePlan}
+        isBusy={isRenaming}
+        onPreview={handlePreviewRename}
+        onApply={handleApplyRename}
+        onOpenChange={handleRenameDialogChange}
+      />
+
       {isScanning && (
         <div className="fixed inset-0 bg-background/95 z-50 animate-in fade-in duration-300">
           <div className="flex h-full">
@@ -2,9 +2,10 @@ import { useState, useMemo, useCallback, memo, useEffect } from "react";
 import { Input } from "./ui/input";
 import { Button } from "./ui/button";
 import { Tooltip, TooltipTrigger, TooltipContent } from "./ui/tooltip";
-import { Search, RefreshCw, Check, AlertTriangle, Eye, EyeOff, Copy, Columns, Files, FileSearch, SearchX } from "lucide-react";
+import { Search, RefreshCw, Check, AlertTriangle, Eye, EyeOff, Copy, Columns, Files, FileSearch, SearchX, Pencil } from "lucide-react";
 import { toast } from "sonner";
 import type { NormalizedEntry, DiscoveredFile } from "../types";
+import { isSecret as isSecretEntry } from "../types";
 import {
   DropdownMenu,
   DropdownMenuContent,
@@ -19,9 +20,13 @@ interface ConfigMatrixProps {
   entries: NormalizedEntry[];
   files: DiscoveredFile[];
   onRescan?: () => void;
+  onRenameKey?: (key: string, spellings: string[]) => void;
 }
 
-export const ConfigMatrix = memo(function ConfigMatrix({ entries, files, onRescan }: ConfigMatrixProps) {
+// Rows are keyed by canonical key, so DATABASE_URL and database.url share a row
+const rowKey = (entry: NormalizedEntry) => entry.canonicalKey || entry.key;
+
+export const ConfigMatrix = memo(function ConfigMatrix({ entries, files, onRescan, onRenameKey }: ConfigMatrixProps) {
   const [searchQuery, setSearchQuery] = useState("");
   const [revealedSecrets, setRevealedSecrets] = useState<Set<string>>(new Set());
   const [visibleFiles, setVisibleFiles] = useState<Set<string>>(new Set(files.map(f => f.path)));
@@ -57,28 +62,36 @@ export const ConfigMatrix = memo(function ConfigMatrix({ entries, files, onResca
     setVisibleFiles(new Set());
   }, []);
 
-  // Get unique keys
-  const uniqueKeys = useMemo(() => {
-    const keys = new Set<string>();
-    entries.forEach((entry) => keys.add(entry.key));
-    return Array.from(keys).sort();
+  // Get unique keys, with every spelling seen for each
+  const spellings = useMemo(() => {
+    const map = new Map<string, string[]>();
+    entries.forEach((entry) => {
+      const names = map.get(rowKey(entry)) ?? [];
+      if (!names.includes(entry.key)) names.push(entry.key);
+      map.set(rowKey(entry), names);
+    });
+    return map;
   }, [entries]);
 
-  // Filter keys by search
+  const uniqueKeys = useMemo(() => Array.from(spellings.keys()).sort(), [spellings]);
+
+  // Filter keys by search, matching any spelling
   const filteredKeys = useMemo(() => {
     if (!searchQuery) return uniqueKeys;
     const query = searchQuery.toLowerCase();
-    return uniqueKeys.filter((key) => key.toLowerCase().includes(query));
-  }, [uniqueKeys, searchQuery]);
+    return uniqueKeys.filter((key) =>
+      spellings.get(key)!.some((name) => name.toLowerCase().includes(query))
+    );
+  }, [uniqueKeys, spellings, searchQuery]);
 
   // Create a map of key -> file -> entry
   const entryMap = useMemo(() => {
     const map = new Map<string, Map<string, NormalizedEntry>>();
     entries.forEach((entry) => {
-      if (!map.has(entry.key)) {
-        map.set(entry.key, new Map());
+      if (!map.has(rowKey(entry))) {
+        map.set(rowKey(entry), new Map());
       }
-      map.get(entry.key)!.set(entry.sourceFile, entry);
+      map.get(rowKey(entry))!.set(entry.sourceFile, entry);
     });
     return map;
   }, [entries]);
@@ -270,7 +283,27 @@ export const ConfigMatrix = memo(function ConfigMatrix({ entries, files, onResca
                     key={keyIdx}
                     className={`${keyIdx % 2 === 0 ? "bg-background" : "bg-muted/30"} hover:bg-accent/50`}
                   >
-                    <td className="px-3 md:px-6 py-2 md:py-3 text-sm font-mono font-medium text-foreground">{key}</td>
+                    <td className="px-3 md:px-6 py-2 md:py-3 text-sm font-mono font-medium text-foreground group">
+                      {spellings.get(key)![0]}
+                      {onRenameKey && (
+                        <Tooltip>
+                          <TooltipTrigger asChild>
+                            <button
+                              onClick={() => onRenameKey(spellings.get(key)![0], spellings.get(key)!)}
+                              className="ml-2 align-middle opacity-0 group-hover:opacity-100 transition-opacity p-1 rounded hover:bg-accent"
+                            >
+                              <Pencil className="h-3 w-3 text-muted-foreground hover:text-foreground" />
+                            </button>
+                          </TooltipTrigger>
+                          <TooltipContent>Rename in every file</TooltipContent>
+                        </Tooltip>
+                      )}
+                      {spellings.get(key)!.length > 1 && (
+                        <span className="block text-xs font-normal text-muted-foreground">
+                          also {spellings.get(key)!.slice(1).join(", ")}
+                        </span>
+                      )}
+                    </td>
                     {activeFiles.map((file, fileIdx) => {
                       const entry = fileEntries.get(file.path);
                       const cellId = `${key}:${file.path}`;
@@ -284,7 +317,7 @@ export const ConfigMatrix = memo(function ConfigMatrix({ entries, files, onResca
                         );
                       }
 
-                      const isSecret = entry.isSecretGuess;
+                      const isSecret = isSecretEntry(entry);
                       const displayValue = isSecret && !isRevealed
                         ? "••••••••"
                         : String(entry.value ?? "");
@@ -312,7 +345,10 @@ export const ConfigMatrix = memo(function ConfigMatrix({ entries, files, onResca
                                     )}
                                   </button>
                                 </TooltipTrigger>
-                                <TooltipContent>Click to reveal/hide secret value</TooltipContent>
+                                <TooltipContent>
+                                  Click to reveal/hide secret value
+                                  {entry.secretRule && ` (${entry.secretRule}, ${Math.round(entry.secretConfidence * 100)}%)`}
+                                </TooltipContent>
                               </Tooltip>
                             ) : (
                               <button className="flex items-center gap-2 text-success hover:text-success/80">
@@ -323,7 +359,7 @@ export const ConfigMatrix = memo(function ConfigMatrix({ entries, files, onResca
                             <Tooltip>
                               <TooltipTrigger asChild>
                                 <button
-                                  onClick={() => copyToClipboard(String(entry.value ?? ""), key)}
+                                  onClick={() => copyToClipboard(String(entry.value ?? ""), entry.key)}
                                   className="opacity-0 group-hover:opacity-100 transition-opacity p-1 rounded hover:bg-accent"
                                 >
                                   <Copy className="h-3 w-3 text-muted-foreground hover:text-foreground" />
@@ -1,19 +1,28 @@
 import { memo, useMemo } from "react";
 import { Badge } from "./ui/badge";
-import { AlertTriangle, FileWarning, Check, Sparkles } from "lucide-react";
+import { Button } from "./ui/button";
+import { AlertTriangle, FileWarning, Check, Sparkles, ShieldAlert, Plus } from "lucide-react";
 import { Accordion, AccordionContent, AccordionItem, AccordionTrigger } from "./ui/accordion";
 import type { ScanIssues } from "../types";
 
 interface IssuesPanelProps {
   issues: ScanIssues;
+  onAddMissingKeys?: (file: string) => void;
 }
 
-export const IssuesPanel = memo(function IssuesPanel({ issues }: IssuesPanelProps) {
+export const IssuesPanel = memo(function IssuesPanel({ issues, onAddMissingKeys }: IssuesPanelProps) {
   const totalIssues = useMemo(
     () =>
       issues.duplicates.length +
       issues.missingByEnvFile.length +
-      issues.parseErrors.length,
+      issues.parseErrors.length +
+      issues.undefinedReferences.length +
+      issues.referenceCycles.length +
+      issues.exposedSecrets.length +
+      issues.schemaViolations.length +
+      issues.policyViolations.length +
+      issues.unusedKeys.length +
+      issues.undefinedKeys.length,
     [issues]
   );
 
@@ -63,7 +72,43 @@ export const IssuesPanel = memo(function IssuesPanel({ issues }: IssuesPanelProp
         </div>
       </div>
 
-      <Accordion type="multiple" defaultValue={["missing", "duplicates"]} className="px-3 md:px-4 pb-4">
+      <Accordion type="multiple" defaultValue={["exposed-secrets", "missing", "duplicates"]} className="px-3 md:px-4 pb-4">
+        {issues.exposedSecrets.length > 0 && (
+          <AccordionItem value="exposed-secrets">
+            <AccordionTrigger className="text-sm">
+              <div className="flex items-center gap-2">
+                <ShieldAlert className="h-4 w-4 text-destructive" />
+                <span>Exposed Secrets</span>
+                <Badge variant="destructive" className="ml-auto">
+                  {issues.exposedSecrets.length}
+                </Badge>
+              </div>
+            </AccordionTrigger>
+            <AccordionContent>
+              <div className="space-y-3">
+                {issues.exposedSecrets.map((exposure, idx) => (
+                  <div
+                    key={idx}
+                    className="rounded-md bg-destructive/5 border border-destructive/20 p-3 shadow-sm hover:shadow-md hover:border-destructive/30 transition-shadow"
+                  >
+                    <p className="text-xs font-semibold mb-1">{exposure.file}</p>
+                    <p className="text-xs text-muted-foreground mb-1">
+                      {exposure.kind === "tracked" ? "Tracked by git" : "Not covered by .gitignore"}
+                    </p>
+                    <div className="space-y-0.5">
+                      {exposure.secrets.map((secret, sidx) => (
+                        <p key={sidx} className="text-xs font-mono text-muted-foreground">
+                          {secret.key}{secret.rule && ` (${secret.rule})`}
+                        </p>
+                      ))}
+                    </div>
+                  </div>
+                ))}
+              </div>
+            </AccordionContent>
+          </AccordionItem>
+        )}
+
         {issues.missingByEnvFile.length > 0 && (
           <AccordionItem value="missing">
             <AccordionTrigger className="text-sm">
@@ -83,6 +128,9 @@ export const IssuesPanel = memo(function IssuesPanel({ issues }: IssuesPanelProp
                     className="rounded-md bg-warning/5 border border-warning/20 p-3 shadow-sm hover:shadow-md hover:border-warning/30 transition-shadow"
                   >
                     <p className="text-xs font-semibold mb-1">{item.file}</p>
+                    <p className="text-xs text-muted-foreground mb-1">
+                      {item.environment ? `${item.environment} · ` : ""}vs {item.comparedTo.join(", ")}
+                    </p>
                     <div className="space-y-0.5">
                       {item.missingKeys.map((key, kidx) => (
                         <p key={kidx} className="text-xs font-mono text-muted-foreground">
@@ -90,6 +138,17 @@ export const IssuesPanel = memo(function IssuesPanel({ issues }: IssuesPanelProp
                         </p>
                       ))}
                     </div>
+                    {onAddMissingKeys && (
+                      <Button
+                        variant="outline"
+                        size="sm"
+                        className="mt-2 h-7 text-xs"
+                        onClick={() => onAddMissingKeys(item.file)}
+                      >
+                        <Plus className="mr-1 h-3 w-3" />
+                        Add missing keys
+                      </Button>
+                    )}
                   </div>
                 ))}
               </div>
@@ -115,8 +174,26 @@ export const IssuesPanel = memo(function IssuesPanel({ issues }: IssuesPanelProp
                     key={idx}
                     className="rounded-md bg-warning/5 border border-warning/20 p-3 shadow-sm hover:shadow-md hover:border-warning/30 transition-shadow"
                   >
-                    <p className="text-xs font-mono font-semibold mb-1">{dup.key}</p>
-                    <p className="text-xs text-muted-foreground">Found in: {dup.files.join(", ")}</p>
+                    <div className="flex items-center gap-2 mb-1">
+                      <p className="text-xs font-mono font-semibold">{dup.key}</p>
+                      {dup.kind !== "sameValue" && (
+                        <Badge variant="outline" className="text-[10px] px-1.5 py-0 text-warning border-warning/30">
+                          {dup.kind === "differentTypes" ? "Different types" : "Different values"}
+                        </Badge>
+                      )}
+                    </div>
+                    {dup.kind === "sameValue" ? (
+                      <p className="text-xs text-muted-foreground">Same value in: {dup.files.join(", ")}</p>
+                    ) : (
+                      <div className="space-y-0.5">
+                        {dup.values.map((v, vidx) => (
+                          <p key={vidx} className="text-xs text-muted-foreground">
+                            {v.file}{v.key !== dup.key && ` (${v.key})`}: <span className="font-mono">{v.value ?? "null"}</span>
+                            {dup.kind === "differentTypes" && ` (${v.inferredType})`}
+                          </p>
+                        ))}
+                      </div>
+                    )}
                   </div>
                 ))}
               </div>
@@ -150,6 +227,198 @@ export const IssuesPanel = memo(function IssuesPanel({ issues }: IssuesPanelProp
             </AccordionContent>
           </AccordionItem>
         )}
+
+        {issues.schemaViolations.length > 0 && (
+          <AccordionItem value="schema-violations">
+            <AccordionTrigger className="text-sm">
+              <div className="flex items-center gap-2">
+                <FileWarning className="h-4 w-4 text-destructive" />
+                <span>Schema Violations</span>
+                <Badge variant="destructive" className="ml-auto">
+                  {issues.schemaViolations.length}
+                </Badge>
+              </div>
+            </AccordionTrigger>
+            <AccordionContent>
+              <div className="space-y-3">
+                {issues.schemaViolations.map((violation, idx) => (
+                  <div
+                    key={idx}
+                    className="rounded-md bg-destructive/5 border border-destructive/20 p-3 shadow-sm hover:shadow-md hover:border-destructive/30 transition-shadow"
+                  >
+                    <p className="text-xs font-mono font-semibold mb-1">
+                      {violation.file}{violation.span && `:${violation.span.line}`}
+                    </p>
+                    {violation.path && (
+                      <p className="text-xs font-mono text-muted-foreground mb-1">{violation.path}</p>
+                    )}
+                    <p className="text-xs text-muted-foreground">{violation.message}</p>
+                  </div>
+                ))}
+              </div>
+            </AccordionContent>
+          </AccordionItem>
+        )}
+
+        {issues.policyViolations.length > 0 && (
+          <AccordionItem value="policy-violations">
+            <AccordionTrigger className="text-sm">
+              <div className="flex items-center gap-2">
+                <AlertTriangle className="h-4 w-4 text-warning" />
+                <span>Policy Violations</span>
+                <Badge variant="secondary" className="ml-auto">
+                  {issues.policyViolations.length}
+                </Badge>
+              </div>
+            </AccordionTrigger>
+            <AccordionContent>
+              <div className="space-y-3">
+                {issues.policyViolations.map((violation, idx) => {
+                  const isError = violation.severity === "error";
+                  return (
+                    <div
+                      key={idx}
+                      className={
+                        isError
+                          ? "rounded-md bg-destructive/5 border border-destructive/20 p-3 shadow-sm hover:shadow-md hover:border-destructive/30 transition-shadow"
+                          : "rounded-md bg-warning/5 border border-warning/20 p-3 shadow-sm hover:shadow-md hover:border-warning/30 transition-shadow"
+                      }
+                    >
+                      <div className="flex items-center gap-2 mb-1">
+                        <p className="text-xs font-mono font-semibold">
+                          {violation.file}{violation.span && `:${violation.span.line}`}
+                        </p>
+                        <Badge
+                          variant="outline"
+                          className={`text-[10px] px-1.5 py-0 ${isError ? "text-destructive border-destructive/30" : "text-warning border-warning/30"}`}
+                        >
+                          {violation.policy}
+                        </Badge>
+                      </div>
+                      <p className="text-xs text-muted-foreground">{violation.message}</p>
+                    </div>
+                  );
+                })}
+              </div>
+            </AccordionContent>
+          </AccordionItem>
+        )}
+
+        {issues.undefinedReferences.length > 0 && (
+          <AccordionItem value="undefined-references">
+            <AccordionTrigger className="text-sm">
+              <div className="flex items-center gap-2">
+                <AlertTriangle className="h-4 w-4 text-warning" />
+                <span>Undefined References</span>
+                <Badge variant="secondary" className="ml-auto">
+                  {issues.undefinedReferences.length}
+                </Badge>
+              </div>
+            </AccordionTrigger>
+            <AccordionContent>
+              <div className="space-y-3">
+                {issues.undefinedReferences.map((ref, idx) => (
+                  <div
+                    key={idx}
+                    className="rounded-md bg-warning/5 border border-warning/20 p-3 shadow-sm hover:shadow-md hover:border-warning/30 transition-shadow"
+                  >
+                    <p className="text-xs font-mono font-semibold mb-1">{ref.key}</p>
+                    <p className="text-xs text-muted-foreground">
+                      Uses {"${"}{ref.reference}{"}"} in {ref.file}
+                    </p>
+                  </div>
+                ))}
+              </div>
+            </AccordionContent>
+          </AccordionItem>
+        )}
+
+        {issues.referenceCycles.length > 0 && (
+          <AccordionItem value="reference-cycles">
+            <AccordionTrigger className="text-sm">
+              <div className="flex items-center gap-2">
+                <FileWarning className="h-4 w-4 text-destructive" />
+                <span>Reference Cycles</span>
+                <Badge variant="destructive" className="ml-auto">
+                  {issues.referenceCycles.length}
+                </Badge>
+              </div>
+            </AccordionTrigger>
+            <AccordionContent>
+              <div className="space-y-3">
+                {issues.referenceCycles.map((cycle, idx) => (
+                  <div
+                    key={idx}
+                    className="rounded-md bg-destructive/5 border border-destructive/20 p-3 shadow-sm hover:shadow-md hover:border-destructive/30 transition-shadow"
+                  >
+                    <p className="text-xs font-mono font-semibold mb-1">{cycle.file}</p>
+                    <p className="text-xs font-mono text-muted-foreground">
+                      {[...cycle.keys, cycle.keys[0]].join(" → ")}
+                    </p>
+                  </div>
+                ))}
+              </div>
+            </AccordionContent>
+          </AccordionItem>
+        )}
+
+        {issues.unusedKeys.length > 0 && (
+          <AccordionItem value="unused-keys">
+            <AccordionTrigger className="text-sm">
+              <div className="flex items-center gap-2">
+                <AlertTriangle className="h-4 w-4 text-warning" />
+                <span>Unused Keys</span>
+                <Badge variant="secondary" className="ml-auto">
+                  {issues.unusedKeys.length}
+                </Badge>
+              </div>
+            </AccordionTrigger>
+            <AccordionContent>
+              <div className="space-y-3">
+                {issues.unusedKeys.map((unused, idx) => (
+                  <div
+                    key={idx}
+                    className="rounded-md bg-warning/5 border border-warning/20 p-3 shadow-sm hover:shadow-md hover:border-warning/30 transition-shadow"
+                  >
+                    <p className="text-xs font-mono font-semibold mb-1">{unused.key}</p>
+                    <p className="text-xs text-muted-foreground">
+                      Defined in {unused.file}{unused.span && `:${unused.span.line}`}, never read by the code
+                    </p>
+                  </div>
+                ))}
+              </div>
+            </AccordionContent>
+          </AccordionItem>
+        )}
+
+        {issues.undefinedKeys.length > 0 && (
+          <AccordionItem value="undefined-keys">
+            <AccordionTrigger className="text-sm">
+              <div className="flex items-center gap-2">
+                <AlertTriangle className="h-4 w-4 text-warning" />
+                <span>Undefined Keys</span>
+                <Badge variant="secondary" className="ml-auto">
+                  {issues.undefinedKeys.length}
+                </Badge>
+              </div>
+            </AccordionTrigger>
+            <AccordionContent>
+              <div className="space-y-3">
+                {issues.undefinedKeys.map((undefinedKey, idx) => (
+                  <div
+                    key={idx}
+                    className="rounded-md bg-warning/5 border border-warning/20 p-3 shadow-sm hover:shadow-md hover:border-warning/30 transition-shadow"
+                  >
+                    <p className="text-xs font-mono font-semibold mb-1">{undefinedKey.key}</p>
+                    <p className="text-xs text-muted-foreground">
+                      Read in {undefinedKey.file}{undefinedKey.span && `:${undefinedKey.span.line}`}, not defined in any env file
+                    </p>
+                  </div>
+                ))}
+              </div>
+            </AccordionContent>
+          </AccordionItem>
+        )}
       </Accordion>
     </aside>
   );
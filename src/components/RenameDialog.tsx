@@ -0,0 +1,126 @@
+import { memo, useEffect, useState } from "react";
+import {
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+  DialogDescription,
+  DialogFooter,
+} from "./ui/dialog";
+import { Button } from "./ui/button";
+import { Input } from "./ui/input";
+import { Checkbox } from "./ui/checkbox";
+import { Label } from "./ui/label";
+import { diffLineClass } from "./SyncDialog";
+import type { RenamePlan } from "../types";
+
+interface RenameDialogProps {
+  fromKey: string | null;
+  // Other spellings of the key, e.g. db.host for DB_HOST
+  spellings: string[];
+  plan: RenamePlan | null;
+  isBusy: boolean;
+  onPreview: (to: string, crossFormat: boolean) => void;
+  onApply: () => void;
+  onOpenChange: (open: boolean) => void;
+}
+
+export const RenameDialog = memo(function RenameDialog({
+  fromKey,
+  spellings,
+  plan,
+  isBusy,
+  onPreview,
+  onApply,
+  onOpenChange,
+}: RenameDialogProps) {
+  const [to, setTo] = useState("");
+  const [crossFormat, setCrossFormat] = useState(true);
+  // The inputs the shown plan was made from; editing them asks for a new preview
+  const [previewed, setPreviewed] = useState("");
+
+  // Start from the current name each time the dialog opens
+  useEffect(() => {
+    setTo(fromKey ?? "");
+    setCrossFormat(true);
+    setPreviewed("");
+  }, [fromKey]);
+
+  const inputs = `${to.trim()}\n${crossFormat}`;
+  const canPreview = to.trim().length > 0 && to.trim() !== fromKey;
+  const canApply =
+    plan !== null && previewed === inputs && plan.changes.length > 0 && plan.conflicts.length === 0;
+
+  return (
+    <Dialog open={fromKey !== null} onOpenChange={onOpenChange}>
+      <DialogContent className="max-w-2xl max-h-[85vh] overflow-y-auto">
+        <DialogHeader>
+          <DialogTitle>Rename {fromKey}</DialogTitle>
+          <DialogDescription>
+            Renames the key in every config file that defines it. Comments and formatting stay as they are.
+          </DialogDescription>
+        </DialogHeader>
+
+        <form
+          className="space-y-3"
+          onSubmit={(event) => {
+            event.preventDefault();
+            if (canPreview) {
+              setPreviewed(inputs);
+              onPreview(to.trim(), crossFormat);
+            }
+          }}
+        >
+          <Input
+            value={to}
+            onChange={(event) => setTo(event.target.value)}
+            placeholder="New name"
+            className="font-mono"
+            autoFocus
+          />
+          <Label className="font-normal">
+            <Checkbox checked={crossFormat} onCheckedChange={(checked) => setCrossFormat(checked === true)} />
+            Also rename other spellings{spellings.length > 1 && ` (${spellings.slice(1).join(", ")})`}
+          </Label>
+          <Button type="submit" variant="outline" size="sm" disabled={isBusy || !canPreview}>
+            Preview changes
+          </Button>
+        </form>
+
+        {plan && (
+          <div className="space-y-3">
+            {plan.changes.length === 0 && plan.conflicts.length === 0 && (
+              <p className="text-sm text-muted-foreground">No file defines {fromKey}.</p>
+            )}
+            {plan.conflicts.map((conflict) => (
+              <p key={conflict} className="text-sm text-destructive">
+                Can't rename in {conflict}
+              </p>
+            ))}
+            {plan.changes.map((change) => (
+              <pre
+                key={change.edit.file}
+                className="rounded-md border border-border bg-muted/30 p-3 text-xs font-mono overflow-x-auto"
+              >
+                {change.edit.diff.split("\n").filter((line) => line.length > 0).map((line, idx) => (
+                  <div key={idx} className={diffLineClass(line)}>
+                    {line}
+                  </div>
+                ))}
+              </pre>
+            ))}
+          </div>
+        )}
+
+        <DialogFooter>
+          <Button variant="outline" onClick={() => onOpenChange(false)} disabled={isBusy}>
+            Cancel
+          </Button>
+          <Button onClick={onApply} disabled={isBusy || !canApply}>
+            {isBusy ? "Working..." : "Apply"}
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+});
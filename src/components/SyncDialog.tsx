@@ -0,0 +1,75 @@
+import { memo } from "react";
+import {
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+  DialogDescription,
+  DialogFooter,
+} from "./ui/dialog";
+import { Button } from "./ui/button";
+import type { SyncEdit } from "../types";
+
+interface SyncDialogProps {
+  edits: SyncEdit[] | null;
+  isApplying: boolean;
+  onApply: () => void;
+  onOpenChange: (open: boolean) => void;
+}
+
+export function diffLineClass(line: string): string {
+  if (line.startsWith("+++") || line.startsWith("---")) {
+    return "text-muted-foreground font-semibold";
+  }
+  if (line.startsWith("@@")) {
+    return "text-primary";
+  }
+  if (line.startsWith("+")) {
+    return "text-success bg-success/10";
+  }
+  if (line.startsWith("-")) {
+    return "text-destructive bg-destructive/10";
+  }
+  return "text-muted-foreground";
+}
+
+export const SyncDialog = memo(function SyncDialog({ edits, isApplying, onApply, onOpenChange }: SyncDialogProps) {
+  const keyCount = edits?.reduce((sum, edit) => sum + edit.added.length, 0) ?? 0;
+
+  return (
+    <Dialog open={edits !== null} onOpenChange={onOpenChange}>
+      <DialogContent className="max-w-2xl max-h-[85vh] overflow-y-auto">
+        <DialogHeader>
+          <DialogTitle>Add missing keys</DialogTitle>
+          <DialogDescription>
+            {keyCount} key{keyCount === 1 ? "" : "s"} will be appended. Existing lines stay as they are.
+          </DialogDescription>
+        </DialogHeader>
+
+        <div className="space-y-3">
+          {edits?.map((edit) => (
+            <pre
+              key={edit.file}
+              className="rounded-md border border-border bg-muted/30 p-3 text-xs font-mono overflow-x-auto"
+            >
+              {edit.diff.split("\n").filter((line) => line.length > 0).map((line, idx) => (
+                <div key={idx} className={diffLineClass(line)}>
+                  {line}
+                </div>
+              ))}
+            </pre>
+          ))}
+        </div>
+
+        <DialogFooter>
+          <Button variant="outline" onClick={() => onOpenChange(false)} disabled={isApplying}>
+            Cancel
+          </Button>
+          <Button onClick={onApply} disabled={isApplying || keyCount === 0}>
+            {isApplying ? "Applying..." : "Apply"}
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+});
@@ -1,10 +1,11 @@
 import { create } from 'zustand';
-import type { ScanResult, DiscoveredFile, NormalizedEntry, ScanIssues, ScanSummary } from '../types';
+import type { ScanResult, DiscoveredFile, Environment, NormalizedEntry, ScanIssues, ScanSummary } from '../types';
 
 interface ScanState {
   // State
   projectPath: string | null;
   files: DiscoveredFile[];
+  environments: Environment[];
   entries: NormalizedEntry[];
   issues: ScanIssues;
   summary: ScanSummary;
@@ -22,16 +23,26 @@ interface ScanState {
 const initialState = {
   projectPath: null,
   files: [],
+  environments: [],
   entries: [],
   issues: {
     duplicates: [],
     missingByEnvFile: [],
     parseErrors: [],
+    undefinedReferences: [],
+    referenceCycles: [],
+    exposedSecrets: [],
+    schemaViolations: [],
+    policyViolations: [],
+    unusedKeys: [],
+    undefinedKeys: [],
   },
   summary: {
     totalFiles: 0,
     totalKeys: 0,
     uniqueKeys: 0,
+    suppressed: 0,
+    baselined: 0,
   },
   isScanning: false,
   error: null,
@@ -45,6 +56,7 @@ export const useScan = create<ScanState>((set) => ({
   setScanResult: (result) => set({
     projectPath: result.root,
     files: result.files,
+    environments: result.environments,
     entries: result.entries,
     issues: result.issues,
     summary: result.summary,
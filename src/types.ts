@@ -4,13 +4,31 @@ export type ExportFormat = 'env' | 'json' | 'yaml';
 
 export type InferredType = 'string' | 'number' | 'boolean' | 'null' | 'unknown';
 
+export interface Span {
+  line: number; // 1-based
+  column: number; // 1-based, in characters
+}
+
 export interface NormalizedEntry {
-  key: string;
+  key: string; // as spelled in the source file
+  canonicalKey: string; // as compared across files, e.g. database.url
   value?: string | number | boolean | null;
   sourceFile: string; // relative to root
   sourceFormat: SourceFormat;
   inferredType: InferredType;
-  isSecretGuess?: boolean;
+  secretConfidence: number; // 0 to 1, see SECRET_THRESHOLD
+  secretRule?: string | null; // pattern name, 'keyword' or 'entropy'
+  span?: Span | null;
+  references?: string[]; // ${VAR} names used by an env value
+  resolvedValue?: string | null; // value with references expanded
+  referencesSecret?: boolean; // resolvedValue includes a referenced secret
+}
+
+// Mirrors jin_core::secrets::SECRET_THRESHOLD
+export const SECRET_THRESHOLD = 0.5;
+
+export function isSecret(entry: NormalizedEntry): boolean {
+  return entry.secretConfidence >= SECRET_THRESHOLD;
 }
 
 export interface DiscoveredFile {
@@ -19,37 +37,235 @@ export interface DiscoveredFile {
   count: number;
 }
 
+export type DuplicateKind = 'sameValue' | 'differentValues' | 'differentTypes';
+
+export interface DuplicateValue {
+  file: string;
+  key: string; // spelling in this file
+  value?: string | null; // secrets are masked
+  inferredType: InferredType;
+  isSecret: boolean;
+}
+
 export interface Duplicate {
   key: string;
+  canonicalKey: string;
   files: string[];
+  kind: DuplicateKind;
+  values: DuplicateValue[];
 }
 
 export interface MissingKeys {
   file: string;
   missingKeys: string[];
+  environment?: string | null;
+  comparedTo: string[]; // template or peer files the keys were expected from
+}
+
+export interface Environment {
+  name: string;
+  files: string[];
 }
 
 export interface ParseError {
   file: string;
   message: string;
+  span?: Span | null;
+}
+
+export interface UndefinedReference {
+  file: string;
+  key: string;
+  reference: string;
+  span?: Span | null;
+}
+
+export interface ReferenceCycle {
+  file: string;
+  keys: string[];
+}
+
+export type Severity = 'off' | 'warning' | 'error';
+
+// An entry that fails a [[policies]] rule from jin.toml
+export interface PolicyViolation {
+  policy: string; // the policy's id
+  severity: Severity;
+  file: string;
+  key: string;
+  message: string;
+  span?: Span | null;
+}
+
+// A YAML, JSON or TOML value that doesn't satisfy the file's JSON Schema
+export interface SchemaViolation {
+  file: string;
+  path: string; // dotted key path, empty for the whole document
+  message: string;
+  schema: string;
+  span?: Span | null;
+}
+
+export type ExposureKind = 'tracked' | 'notIgnored';
+
+export interface ExposedSecret {
+  key: string;
+  rule?: string | null;
+  span?: Span | null;
+}
+
+// A file with real secret values that git tracks, or would pick up with `git add`
+export interface SecretExposure {
+  file: string;
+  kind: ExposureKind;
+  secrets: ExposedSecret[];
+}
+
+// An env key that no indexed source file reads ([code] in jin.toml)
+export interface UnusedKey {
+  key: string;
+  file: string;
+  span?: Span | null;
+}
+
+// A variable read by source code that no env file defines; file is the source file
+export interface UndefinedKey {
+  key: string;
+  file: string;
+  span?: Span | null;
 }
 
 export interface ScanIssues {
   duplicates: Duplicate[];
   missingByEnvFile: MissingKeys[];
   parseErrors: ParseError[];
+  undefinedReferences: UndefinedReference[];
+  referenceCycles: ReferenceCycle[];
+  exposedSecrets: SecretExposure[];
+  schemaViolations: SchemaViolation[];
+  policyViolations: PolicyViolation[];
+  unusedKeys: UnusedKey[];
+  undefinedKeys: UndefinedKey[];
+}
+
+export type IssueCategory =
+  | 'duplicates'
+  | 'valueConflicts'
+  | 'missingKeys'
+  | 'parseErrors'
+  | 'undefinedReferences'
+  | 'referenceCycles'
+  | 'exposedSecrets'
+  | 'schemaViolations'
+  | 'policies'
+  | 'unusedKeys'
+  | 'undefinedKeys';
+
+// One entry of the flat issue list, with a stable code such as JIN002
+export interface Issue {
+  code: string;
+  category: IssueCategory;
+  severity: Severity;
+  message: string;
+  file: string;
+  key?: string | null;
+  span?: Span | null;
+  fix?: string | null;
 }
 
 export interface ScanSummary {
   totalFiles: number;
   totalKeys: number;
   uniqueKeys: number;
+  suppressed: number; // silenced by jin-ignore comments or [[allow]] entries
+  baselined: number; // known issues recorded in a baseline file
 }
 
 export interface ScanResult {
   root: string;
   files: DiscoveredFile[];
+  environments: Environment[];
   entries: NormalizedEntry[];
   issues: ScanIssues;
+  diagnostics: Issue[];
   summary: ScanSummary;
 }
+
+// Write-back of missing keys (see `sync` in jin-core)
+export interface SyncOptions {
+  files: string[]; // empty = every file with missing keys
+  placeholder: string;
+  templateValues: boolean; // copy non-secret values from the template
+}
+
+export interface SyncedKey {
+  key: string;
+  value: string;
+  fromTemplate: boolean;
+}
+
+export interface SyncEdit {
+  file: string;
+  added: SyncedKey[];
+  original: string; // apply refuses to write if the file changed since the preview
+  updated: string;
+  diff: string; // unified diff, secret values masked
+}
+
+// Format-preserving key edits (see `edit` in jin-core); keys are flattened paths like `db.host`
+export type KeyEdit =
+  | { op: 'set'; key: string; value: string | number | boolean | null }
+  | { op: 'rename'; key: string; to: string }
+  | { op: 'delete'; key: string };
+
+export interface FileEdit {
+  file: string;
+  original: string; // apply refuses to write if the file changed since the preview
+  updated: string;
+  diff: string; // unified diff
+}
+
+// Bulk rename across files (see `rename` in jin-core)
+export interface RenameOptions {
+  from: string;
+  to: string;
+  crossFormat: boolean; // also rename other spellings, e.g. db.host for DB_HOST
+  files: string[]; // empty = every scanned file
+}
+
+export interface RenamedKey {
+  from: string;
+  to: string;
+}
+
+export interface RenameChange {
+  renamed: RenamedKey[];
+  edit: FileEdit; // diff has secret values masked
+}
+
+export interface RenamePlan {
+  changes: RenameChange[];
+  conflicts: string[]; // files that define the key but can't be renamed
+}
+
+// Config diff (see `compare` in jin-core)
+export type CompareSide =
+  | { kind: "file"; path: string }
+  | { kind: "environment"; name: string }
+  | { kind: "all" }; // every file; two projects are compared file by file
+
+export interface KeyChange {
+  key: string;
+  canonicalKey: string;
+  file?: string | null; // set when two projects are compared
+  left?: DuplicateValue | null; // absent for added keys; secrets are masked
+  right?: DuplicateValue | null; // absent for removed keys
+}
+
+export interface ConfigDiff {
+  added: KeyChange[];
+  removed: KeyChange[];
+  changedValue: KeyChange[];
+  changedType: KeyChange[];
+  unchanged: number;
+}